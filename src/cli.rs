@@ -0,0 +1,787 @@
+use crate::{configuration, daemon, hook, interface, metrics, notification, ownership, prefix, rest_api, state, status};
+
+/**
+ * Entry point of the `cloudflare_dynamic_dns` binary: one check, or the daemon when configured
+ **/
+pub async fn main() {
+    use env_logger::{Builder, Env};
+    Builder::from_env(Env::default().default_filter_or("info")).init();
+
+    let configuration = Configuration::new()
+        .unwrap_or_else(|error| {
+            use log::error;
+            error!(target: "main", "{error}");
+            panic!("{error}");
+        });
+
+    if let Some(format) = configuration.print_config {
+        let Some(effective) = configuration.to_redacted_string(format) else {
+            use log::error;
+            error!(target: "main", "Failed to serialize effective configuration as {format:?}");
+            panic!("Failed to serialize effective configuration as {format:?}");
+        };
+        println!("{effective}");
+        return;
+    }
+
+    if configuration.daemon {
+        daemon::start(configuration).await;
+        return;
+    }
+
+    let mut state = State::load(configuration.config.state_file.as_deref());
+    if let Err(error_message) = run(&configuration, &mut state).await {
+        panic!("{error_message}");
+    }
+}
+
+/**
+ * One check of every configured domain name followed by its notifications, errors are logged before returned
+ **/
+pub(crate) async fn run(configuration: &Configuration, state: &mut State) -> Result<(), &'static str> {
+    use std::time::Instant;
+    let (timestamp, started) = (state::now(), Instant::now());
+    let result = check(configuration, state).await;
+    // Failures of single records and sources are described already
+    if let Err(error_message) = result && state.failures.is_empty() {
+        state.fail(error_message.to_string());
+    }
+    let succeeded = state.failures.is_empty();
+    if !succeeded {
+        hook::failed(&configuration.config, &state.failures).await;
+    }
+    notification::notify(configuration, state).await;
+    metrics::checked(succeeded, state::now(), state.failed_checks, configuration.config.healthz_failures);
+    if let Some(state_file) = &configuration.config.state_file {
+        state.save(state_file);
+    }
+    // Taken even when not written, outcomes of a check never leak into the next
+    let status = Status { timestamp, succeeded, duration_seconds: started.elapsed().as_secs_f64(), ..std::mem::take(&mut state.status) };
+    if let Some(status_file) = &configuration.config.status_file {
+        status.write(status_file, configuration.config.status_format);
+    }
+    result
+}
+
+async fn check(configuration: &Configuration, state: &mut State) -> Result<(), &'static str> {
+    let (cloudflare_api, records) = match connect(configuration).await {
+        Ok(connected) => connected,
+        Err(error_message) => {
+            // Nothing is known about any record, every configured one fails along
+            for domain_name in &configuration.cloudflare.domain_names {
+                let record_type = domain_name.domain_type.to_string();
+                state.status.record(&domain_name.name, &record_type, Outcome::Failed, Some(error_message.to_string()));
+            }
+            return Err(error_message);
+        }
+    };
+
+    let domain_names = &expand(&configuration.cloudflare, &records);
+    if let Some(is_proxied) = configuration.proxied {
+        handle_proxied(&cloudflare_api, &records, domain_names, is_proxied, &configuration.config, state).await;
+    }
+    else {
+        // Every source in use detects once, shared by all domain names it feeds
+        let mut sources = Vec::new();
+        for domain_name in domain_names {
+            let source = domain_name.effective_source();
+            if !sources.contains(&source) {
+                sources.push(source);
+            }
+        }
+
+        let mut result = Ok(());
+        for source in sources {
+            // A failing source leaves its own domain names untouched only
+            use std::time::Instant;
+            let started = Instant::now();
+            let detected = detect(configuration, &source).await;
+            let duration_seconds = started.elapsed().as_secs_f64();
+            let domain_names = domain_names.iter()
+                .filter(|domain_name| domain_name.effective_source() == source)
+                .collect::<Vec<_>>();
+            let ip = match detected {
+                Ok(ip) => {
+                    metrics::address(&source.to_string(), ip.v4(), ip.v6());
+                    state.status.sources.push(SourceStatus { source: source.to_string(), v4: ip.v4(), v6: ip.v6(), duration_seconds, error: None });
+                    ip
+                }
+                Err(error_message) => {
+                    state.fail(format!("{source}: {error_message}"));
+                    state.status.sources.push(SourceStatus {
+                        source: source.to_string(), v4: None, v6: None, duration_seconds, error: Some(error_message.to_string())
+                    });
+                    for domain_name in domain_names {
+                        let record_type = domain_name.domain_type.to_string();
+                        state.status.record(&domain_name.name, &record_type, Outcome::Failed, Some(error_message.to_string()));
+                    }
+                    result = result.and(Err(error_message));
+                    continue;
+                }
+            };
+
+            handle_ip_update(&cloudflare_api, &records, &domain_names, &ip, &configuration.config, state).await;
+        }
+        result?;
+    }
+    Ok(())
+}
+
+/**
+ * Client of the zone along with its records, once the token is verified
+ **/
+async fn connect(configuration: &Configuration) -> Result<(CloudflareApi, Vec<Record>), &'static str> {
+    let cloudflare_api = cloudflare_api(configuration)?;
+    if let Err(error) = metrics::cloudflare("verify_token", cloudflare_api.verify_user_token().await) {
+        use rest_api::cloudflare::error::Error;
+        let error_message = match error {
+            Error::Network => { "Network error occurred when verifying token availability from Cloudflare api" }
+            Error::Unauthorized => { "Unauthorized responded when verifying token availability from Cloudflare api" }
+            Error::DecodeResponse => { "Unknown response body responded when verifying token availability from Cloudflare api" }
+            Error::Unknown => { "Unknown error occurred when verifying token availability from Cloudflare api" }
+            _ => unreachable!("Unreachable condition met when handing error from verifying token availability from Cloudflare api")
+        };
+
+        use log::error;
+        error!(target: "main", "{error_message}");
+        return Err(error_message);
+    };
+
+    let records = metrics::cloudflare("list_records", cloudflare_api.list_record().await)
+        .map_err(|error| {
+            use rest_api::cloudflare::error::Error;
+            let error_message = match error {
+                Error::Internal => { "Internal error caused due to invalid request content sent to Cloudflare api" }
+                Error::Network => { "Network error occurred when requesting record list from Cloudflare api" }
+                Error::Unauthorized => { "Unauthorized responded when requesting record list from Cloudflare api" }
+                Error::InvalidZone => { "Invalid zone responded when requesting record list from Cloudflare api" }
+                Error::Server => { "Server error occurred when requesting record list from Cloudflare api" }
+                Error::DecodeResponse => { "Deserializing error occurred when processing listed records response from Cloudflare api" }
+                Error::Unknown => { "Unknown error occurred when requesting record list from Cloudflare api" }
+                _ => unreachable!("Unreachable condition met when handing error from requesting list of records from Cloudflare api")
+            };
+
+            use log::error;
+            error!(target: "main", "{error_message}");
+            error_message
+        })?;
+    Ok((cloudflare_api, records))
+}
+
+use rest_api::{cloudflare::record::Record, CloudflareApi, IpSBApi};
+use configuration::{cloudflare::{domain_name::DomainName, Cloudflare}, config::{provider::Provider, Config}, Configuration};
+use notification::Event;
+use state::State;
+use status::{Outcome, SourceStatus, Status};
+
+pub(crate) fn cloudflare_api(configuration: &Configuration) -> Result<CloudflareApi, &'static str> {
+    let (token, zone, (total, connect, read)) = (
+        &configuration.cloudflare.token, &configuration.cloudflare.zone, configuration.config.cloudflare_timeout.durations()
+    );
+    let mut builder = CloudflareApi::builder(token, zone)
+        .timeout(total)
+        .connect_timeout(connect)
+        .read_timeout(read)
+        .proxy(configuration.config.cloudflare_proxy.clone());
+    if let Some(base_url) = &configuration.config.cloudflare_api_url {
+        builder = builder.base_url(base_url);
+    }
+    builder.build().map_err(|error| build_error_message(error, "Cloudflare api"))
+}
+
+fn ip_sb_api(configuration: &Configuration, provider: Option<&Provider>) -> Result<IpSBApi, &'static str> {
+    let (total, connect, read) = configuration.config.ip_sb_timeout.durations();
+    let (v4_url, v6_url) = (
+        provider.and_then(|provider| provider.url_v4.as_deref())
+            .or(configuration.config.ip_sb_url_v4.as_deref())
+            .unwrap_or(IpSBApi::DEFAULT_V4_URL),
+        provider.and_then(|provider| provider.url_v6.as_deref())
+            .or(configuration.config.ip_sb_url_v6.as_deref())
+            .unwrap_or(IpSBApi::DEFAULT_V6_URL)
+    );
+    IpSBApi::builder()
+        .v4_url(v4_url)
+        .v6_url(v6_url)
+        .timeout(total)
+        .connect_timeout(connect)
+        .read_timeout(read)
+        .proxy(configuration.config.ip_sb_proxy.clone())
+        .binding(provider.map(Provider::binding).unwrap_or_default())
+        .build()
+        .map_err(|error| build_error_message(error, "ip.sb api"))
+}
+
+fn build_error_message(error: rest_api::builder::Error, api: &str) -> &'static str {
+    use rest_api::builder::Error;
+    let error_message = match error {
+        Error::InvalidToken => { "Invalid token, it cannot be sent as an authorization header" }
+        Error::InvalidProxy => { "Invalid proxy url, it cannot be parsed" }
+        Error::ClientBinding => { "A binding cannot be applied to a given reqwest client" }
+        Error::Client => { "Error occurred when building reqwest client" }
+    };
+
+    use log::error;
+    error!(target: "main", "{error_message} ({api})");
+    error_message
+}
+
+#[inline]
+async fn handle_proxied(
+    cloudflare_api: &CloudflareApi, records: &[Record], domain_names: &[DomainName], is_proxied: bool, config: &Config, state: &mut State
+) {
+    for domain_name in domain_names {
+        let record = records.iter()
+            .find(|record| record.domain_name == domain_name.name && record.record_type == domain_name.domain_type);
+        let record_type = domain_name.domain_type.to_string();
+        let Some(record) = record else {
+            state.status.record(&domain_name.name, &record_type, Outcome::Failed, Some(String::from("No record to toggle proxied on")));
+            continue;
+        };
+        if record.proxied == is_proxied {
+            state.status.record(&domain_name.name, &record_type, Outcome::Unchanged, None);
+        }
+        else if !record.proxiable {
+            use log::warn;
+            warn!(target: "main", "Leave {} ({}) unproxied as Cloudflare cannot proxy it", record.domain_name, record.record_type);
+            state.status.record(&domain_name.name, &record_type, Outcome::Skipped, None);
+        }
+        else if handle_ownership(cloudflare_api, record, domain_name, config, state).await {
+            let event = Event::Modified {
+                name: domain_name.name.clone(), record_type, old: format!("proxied={}", record.proxied), new: format!("proxied={is_proxied}")
+            };
+            if !approve(config, state, &event).await {
+                continue;
+            }
+            let result = handle_record_proxied_update(cloudflare_api, &record.id, is_proxied).await;
+            report(config, state, result, event).await;
+        }
+    }
+}
+
+#[inline]
+async fn handle_record_proxied_update(cloudflare_api: &CloudflareApi, record_id: &String, is_proxied: bool) -> Result<(), &'static str> {
+    match metrics::cloudflare("update_record", cloudflare_api.update_record_proxied(record_id, is_proxied).await) {
+        Ok(record) => {
+            if record.id == *record_id && record.proxied == is_proxied {
+                use log::info;
+                info!(target: "main", "Update {} ({}) proxied -> {}", record.domain_name, record.record_type, is_proxied);
+                Ok(())
+            }
+            else {
+                use log::error;
+                error!(target: "main", "Failed to update {} ({}) record proxied", record.domain_name, record.record_type);
+                Err("Unexpected record responded when updating record proxied value from Cloudflare api")
+            }
+        }
+        Err(error) => {
+            use rest_api::cloudflare::error::Error;
+            let error_message = match error {
+                Error::Internal => { "Internal error caused due to invalid proxied update request sent to Cloudflare api" }
+                Error::Network => { "Network error occurred when updating record proxied value from Cloudflare api" }
+                Error::Unauthorized => { "Unauthorized responded when updating record proxied value from Cloudflare api" }
+                Error::InvalidZone => { "Invalid zone responded when updating record proxied value from Cloudflare api" }
+                Error::InvalidRecord => { "Invalid record id responded when updating record proxied value from Cloudflare api" }
+                Error::Server => { "Server error occurred when updating record proxied value from Cloudflare api" }
+                Error::DecodeResponse => { "Deserializing error occurred when processing record proxied update response from Cloudflare api" }
+                Error::Unknown => { "Unknown error occurred when updating record proxied value from Cloudflare api" }
+            };
+
+            use log::error;
+            error!(target: "main", "{error_message}");
+            Err(error_message)
+        }
+    }
+}
+
+/**
+ * Configured domain names followed by the records matching `patterns` not configured already
+ **/
+fn expand(cloudflare: &Cloudflare, records: &[Record]) -> Vec<DomainName> {
+    use configuration::cloudflare::domain_name::DomainType;
+    use rest_api::cloudflare::record::RecordType;
+    let mut domain_names = cloudflare.domain_names.clone();
+    for record in records {
+        let domain_type = match record.record_type {
+            RecordType::A => DomainType::A,
+            RecordType::AAAA => DomainType::AAAA,
+            _ => continue
+        };
+        let is_configured = domain_names.iter()
+            .any(|domain_name| domain_name.name.eq_ignore_ascii_case(&record.domain_name) && domain_name.domain_type == domain_type);
+        if is_configured {
+            continue;
+        }
+        let pattern = cloudflare.patterns.iter()
+            .find(|pattern| pattern.matches(&record.domain_name, domain_type, &record.tags));
+        if let Some(pattern) = pattern {
+            domain_names.push(pattern.domain_name(&record.domain_name, record.proxied, record.time_to_live));
+        }
+    }
+    domain_names
+}
+
+use std::net::IpAddr;
+use rest_api::ip_sb::ip::IP;
+use configuration::cloudflare::domain_name::source::Source;
+
+/**
+ * Addresses of `source`, errors are logged before returned
+ **/
+async fn detect(configuration: &Configuration, source: &Source) -> Result<IP, &'static str> {
+    let provider = match source {
+        Source::Static(address) => return Ok(match address {
+            IpAddr::V4(v4) => IP::V4(v4.to_string()),
+            IpAddr::V6(v6) => IP::V6(v6.to_string())
+        }),
+        Source::Interface(name) => return interface::get_ip(name)
+            .map_err(|error| {
+                use interface::Error;
+                let error_message = match error {
+                    Error::Unsupported => { "Reading interface addresses is not supported on this platform" }
+                    Error::Read => { "Error occurred when reading interface addresses" }
+                    Error::NoAddress => { "No usable address found on interface" }
+                };
+
+                use log::error;
+                error!(target: "main", "{error_message} ({source})");
+                error_message
+            }),
+        Source::DelegatedPrefix(lease) => {
+            use std::fs::read_to_string;
+            let error_message = match read_to_string(lease) {
+                Ok(lease) => match prefix::delegated_prefix(&lease) {
+                    Some(prefix) => return Ok(IP::V6(prefix.to_string())),
+                    None => "No delegated prefix found in lease file"
+                },
+                Err(_) => "Error occurred when reading delegated prefix lease file"
+            };
+
+            use log::error;
+            error!(target: "main", "{error_message} ({source})");
+            return Err(error_message);
+        }
+        Source::Provider(name) => configuration.config.providers.iter().find(|declared| declared.name == *name),
+        Source::Wan => None
+    };
+
+    let ip_sb_api = ip_sb_api(configuration, provider)?;
+    use std::time::Instant;
+    let started = Instant::now();
+    let result = ip_sb_api.get_ip().await;
+    metrics::detected(provider.map_or("wan", |provider| provider.name.as_str()), started.elapsed());
+    result
+        .map_err(|error| {
+            use rest_api::ip_sb::error::Error;
+            let error_message = match error {
+                Error::Network => { "Network error occurred when sending request to ip.sb api" }
+                Error::Server => { "Server error responded when requesting ip address from ip.sb api" }
+                Error::DecodeResponse => { "Deserializing error occurred when processing ip.sb api json response" }
+                Error::Unknown => { "Unknown error occurred when requesting ip address from ip.sb api" }
+            };
+
+            use log::error;
+            error!(target: "main", "{error_message} ({source})");
+            error_message
+        })
+}
+
+#[inline]
+async fn handle_ip_update(
+    cloudflare_api: &CloudflareApi, records: &[Record], domain_names: &[&DomainName], ip: &IP, config: &Config, state: &mut State
+) {
+    for domain_name in domain_names {
+        let record = records.iter()
+            .find(|record| record.domain_name == domain_name.name && record.record_type == domain_name.domain_type);
+        let address = address_of(domain_name, ip);
+        let key = format!("{}/{}", domain_name.name, domain_name.domain_type);
+        if address.is_some() {
+            state.present(&key);
+        }
+
+        if let Some(record) = record {
+            if !handle_ownership(cloudflare_api, record, domain_name, config, state).await {
+                continue;
+            }
+            if let Some(address) = address {
+                // Rewriting an unchanged address only churns, e.g. a privacy address rotated within the same prefix
+                if address.parse::<IpAddr>().is_ok_and(|address| record.value.parse() == Ok(address)) {
+                    use log::debug;
+                    debug!(target: "main", "{} ({}) record ip unchanged", record.domain_name, record.record_type);
+                    state.status.record(&domain_name.name, &domain_name.domain_type.to_string(), Outcome::Unchanged, None);
+                    handle_record_fields(cloudflare_api, record, domain_name, &address, false, config, state).await;
+                    continue;
+                }
+                let event = Event::Changed {
+                    name: record.domain_name.clone(), record_type: format!("{}", record.record_type), old: record.value.clone(), new: address.clone()
+                };
+                if !approve(config, state, &event).await {
+                    continue;
+                }
+                // Do update ip
+                let result = handle_record_ip_update(cloudflare_api, &record.id, &address).await;
+                let is_changed = result.is_ok();
+                report(config, state, result, event).await;
+                if is_changed {
+                    handle_record_fields(cloudflare_api, record, domain_name, &address, true, config, state).await;
+                }
+            }
+            else if config.unavailable_hide && !domain_name.pattern {
+                handle_unavailable(cloudflare_api, record, domain_name, config, state, &key).await;
+            }
+            else {
+                handle_missing_address(domain_name, state);
+            }
+        }
+        else if let Some(address) = address {
+            let event = Event::Created {
+                name: domain_name.name.clone(), record_type: domain_name.domain_type.to_string(), address: address.clone()
+            };
+            if !approve(config, state, &event).await {
+                continue;
+            }
+            // Do record creation
+            let result = handle_record_create(cloudflare_api, domain_name, &address).await;
+            report(config, state, result, event).await;
+        }
+        else {
+            handle_missing_address(domain_name, state);
+        }
+    }
+}
+
+/**
+ * The source of `domain_name` did not detect an address of its family, the record is left as it is
+ **/
+fn handle_missing_address(domain_name: &DomainName, state: &mut State) {
+    let error_message = format!("No {} address detected for the record", domain_name.domain_type);
+    state.status.record(&domain_name.name, &domain_name.domain_type.to_string(), Outcome::Failed, Some(error_message));
+}
+
+/**
+ * Delete or park `record` once its address family stayed missing for `unavailable-checks` checks and `unavailable-grace` seconds
+ **/
+async fn handle_unavailable(
+    cloudflare_api: &CloudflareApi, record: &Record, domain_name: &DomainName, config: &Config, state: &mut State, key: &str
+) {
+    let (checks, elapsed) = state.missing(key, state::now());
+    if checks < config.unavailable_checks || elapsed < config.unavailable_grace {
+        use log::info;
+        info!(
+            target: "main",
+            "Keep {} ({}) record while its address is missing for {checks} checks and {elapsed} seconds",
+            record.domain_name, record.record_type
+        );
+        state.status.record(&domain_name.name, &domain_name.domain_type.to_string(), Outcome::Skipped, None);
+        return;
+    }
+
+    use configuration::config::unavailable::UnavailableAction;
+    match config.unavailable_action {
+        UnavailableAction::Delete => {
+            let event = Event::Deleted {
+                name: record.domain_name.clone(), record_type: format!("{}", record.record_type), old: record.value.clone()
+            };
+            if !approve(config, state, &event).await {
+                return;
+            }
+            let result = handle_record_delete(cloudflare_api, &record.id).await;
+            report(config, state, result, event).await;
+            state.present(key);
+        }
+        UnavailableAction::Park => {
+            use configuration::cloudflare::domain_name::DomainType;
+            let placeholder = match domain_name.domain_type {
+                DomainType::A => config.park.v4.map(IpAddr::V4),
+                DomainType::AAAA => config.park.v6.map(IpAddr::V6)
+            };
+            let record_type = domain_name.domain_type.to_string();
+            let Some(placeholder) = placeholder else {
+                use log::warn;
+                warn!(target: "main", "Leave {} ({}) record as no park address is configured for it", record.domain_name, record.record_type);
+                let error_message = format!("No park address configured for {record_type}");
+                state.status.record(&domain_name.name, &record_type, Outcome::Failed, Some(error_message));
+                return;
+            };
+            if record.value.parse() == Ok(placeholder) {
+                state.status.record(&domain_name.name, &record_type, Outcome::Unchanged, None);
+            }
+            else {
+                let event = Event::Changed {
+                    name: record.domain_name.clone(),
+                    record_type: format!("{}", record.record_type),
+                    old: record.value.clone(),
+                    new: placeholder.to_string()
+                };
+                if !approve(config, state, &event).await {
+                    return;
+                }
+                let result = handle_record_ip_update(cloudflare_api, &record.id, &placeholder.to_string()).await;
+                report(config, state, result, event).await;
+            }
+        }
+    }
+}
+
+/**
+ * Whether `record` may be overwritten or deleted, a record without the ownership marker is marked first when adopted
+ **/
+async fn handle_ownership(cloudflare_api: &CloudflareApi, record: &Record, domain_name: &DomainName, config: &Config, state: &mut State) -> bool {
+    if ownership::is_owned(record) {
+        return true;
+    }
+    if !domain_name.adopt {
+        use log::warn;
+        warn!(
+            target: "main",
+            "Leave {} ({}) untouched as it was not created by cloudflare_dynamic_dns, set `adopt: true` to manage it",
+            record.domain_name, record.record_type
+        );
+        state.status.record(&domain_name.name, &domain_name.domain_type.to_string(), Outcome::Skipped, None);
+        return false;
+    }
+
+    let comment = ownership::mark(record.comment.as_deref());
+    let event = Event::Modified {
+        name: domain_name.name.clone(),
+        record_type: domain_name.domain_type.to_string(),
+        old: format!("comment={:?}", record.comment.as_deref().unwrap_or_default()),
+        new: format!("comment={comment:?}")
+    };
+    if !approve(config, state, &event).await {
+        return false;
+    }
+    let result = match metrics::cloudflare("update_record", cloudflare_api.update_record_comment(&record.id, &comment).await) {
+        Ok(_) => {
+            use log::info;
+            info!(target: "main", "Adopt {} ({}) record", record.domain_name, record.record_type);
+            Ok(())
+        }
+        Err(_) => {
+            use log::error;
+            error!(target: "main", "Failed to mark {} ({}) record as adopted", record.domain_name, record.record_type);
+            Err("Failed to mark record as adopted through Cloudflare api")
+        }
+    };
+    let is_adopted = result.is_ok();
+    report(config, state, result, event).await;
+    is_adopted
+}
+
+/**
+ * Ask `pre-change` about `event`, a vetoed change is skipped
+ **/
+pub(crate) async fn approve(config: &Config, state: &mut State, event: &Event) -> bool {
+    let approved = hook::approve(config, event).await;
+    if !approved && let Some((name, record_type)) = event.record() {
+        state.status.record(name, record_type, Outcome::Skipped, None);
+    }
+    approved
+}
+
+/**
+ * Run `on-change` and notify `event` once done, or fail the check with what was attempted
+ **/
+pub(crate) async fn report(config: &Config, state: &mut State, result: Result<(), &'static str>, event: Event) {
+    let Some((name, record_type)) = event.record() else { return; };
+    let (name, record_type) = (name.to_string(), record_type.to_string());
+    match result {
+        Ok(()) => {
+            let outcome = match event {
+                Event::Created { .. } => Outcome::Created,
+                Event::Deleted { .. } => Outcome::Deleted,
+                _ => Outcome::Updated
+            };
+            state.status.record(&name, &record_type, outcome, None);
+            hook::changed(config, &event).await;
+            metrics::changed(&event, state::now());
+            state.notify(event);
+        }
+        Err(error_message) => {
+            state.status.record(&name, &record_type, Outcome::Failed, Some(error_message.to_string()));
+            state.fail(format!("{}: {error_message}", event.text()));
+        }
+    }
+}
+
+/**
+ * Bring comment, tags and ttl of `record` in line with `domain_name`, a comment rendering `{time}` only once `is_changed`
+ **/
+async fn handle_record_fields(
+    cloudflare_api: &CloudflareApi, record: &Record, domain_name: &DomainName, address: &str, is_changed: bool, config: &Config, state: &mut State
+) {
+    let comment = domain_name.comment.as_deref()
+        .filter(|template| is_changed || !ownership::is_volatile(template))
+        .map(|template| ownership::comment(template, domain_name, address, state::now()))
+        .filter(|comment| record.comment.as_deref() != Some(comment.as_str()));
+    let tags = domain_name.tags.as_deref().filter(|tags| {
+        let (mut declared, mut carried) = (tags.to_vec(), record.tags.clone());
+        declared.sort();
+        carried.sort();
+        declared != carried
+    });
+    // Cloudflare keeps proxied records at 1 (automatic) whatever is asked for
+    let time_to_live = Some(domain_name.time_to_live).filter(|time_to_live| !record.proxied && *time_to_live != record.time_to_live);
+    if comment.is_none() && tags.is_none() && time_to_live.is_none() {
+        return;
+    }
+
+    let (mut old, mut new) = (Vec::new(), Vec::new());
+    if let Some(comment) = &comment {
+        old.push(format!("comment={:?}", record.comment.as_deref().unwrap_or_default()));
+        new.push(format!("comment={comment:?}"));
+    }
+    if let Some(tags) = tags {
+        old.push(format!("tags={}", record.tags.join(",")));
+        new.push(format!("tags={}", tags.join(",")));
+    }
+    if let Some(time_to_live) = time_to_live {
+        old.push(format!("ttl={}", record.time_to_live));
+        new.push(format!("ttl={time_to_live}"));
+    }
+    let event = Event::Modified {
+        name: domain_name.name.clone(), record_type: domain_name.domain_type.to_string(), old: old.join(" "), new: new.join(" ")
+    };
+    if !approve(config, state, &event).await {
+        return;
+    }
+
+    let result = cloudflare_api.update_record_fields(&record.id, comment.as_deref(), tags, time_to_live).await;
+    let result = match metrics::cloudflare("update_record", result) {
+        Ok(_) => {
+            use log::info;
+            info!(target: "main", "Update {} ({}) record comment, tags and ttl", record.domain_name, record.record_type);
+            Ok(())
+        }
+        Err(_) => {
+            use log::error;
+            error!(target: "main", "Failed to update {} ({}) record comment, tags and ttl", record.domain_name, record.record_type);
+            Err("Failed to update record comment, tags and ttl through Cloudflare api")
+        }
+    };
+    report(config, state, result, event).await;
+}
+
+/**
+ * The address `domain_name` publishes, an `ipv6-suffix` replaces the interface identifier of the detected ipv6 address
+ **/
+fn address_of(domain_name: &DomainName, ip: &IP) -> Option<String> {
+    use configuration::cloudflare::domain_name::DomainType;
+    match domain_name.domain_type {
+        DomainType::A => ip.v4(),
+        DomainType::AAAA => {
+            let v6 = ip.v6()?;
+            let Some(ipv6_suffix) = &domain_name.ipv6_suffix else { return Some(v6); };
+            use std::net::Ipv6Addr;
+            let address = v6.parse::<Ipv6Addr>().ok()?;
+            Some(prefix::combine(&address, ipv6_suffix).to_string())
+        }
+    }
+}
+
+#[inline]
+async fn handle_record_ip_update(cloudflare_api: &CloudflareApi, record_id: &String, ip: &String) -> Result<(), &'static str> {
+    match metrics::cloudflare("update_record", cloudflare_api.update_record_value(record_id, ip).await) {
+        Ok(record) => {
+            if record.id == *record_id && record.value == *ip {
+                use log::info;
+                info!(target: "main", "Update {} ({}) record ip -> {}", record.domain_name, record.record_type, ip);
+                Ok(())
+            }
+            else {
+                use log::error;
+                error!(target: "main", "Failed to update {} ({}) record ip", record.domain_name, record.record_type);
+                Err("Unexpected record responded when updating record ip value from Cloudflare api")
+            }
+        }
+        Err(error) => {
+            use rest_api::cloudflare::error::Error;
+            let error_message = match error {
+                Error::Internal => { "Internal error caused due to invalid ip update request sent to Cloudflare api" }
+                Error::Network => { "Network error occurred when updating record ip value from Cloudflare api" }
+                Error::Unauthorized => { "Unauthorized responded when updating record ip value from Cloudflare api" }
+                Error::InvalidZone => { "Invalid zone responded when updating record ip value from Cloudflare api" }
+                Error::InvalidRecord => { "Invalid record id responded when updating record ip value from Cloudflare api" }
+                Error::Server => { "Server error occurred when updating record ip value from Cloudflare api" }
+                Error::DecodeResponse => { "Deserializing error occurred when processing record ip update response from Cloudflare api" }
+                Error::Unknown => { "Unknown error occurred when updating record ip value from Cloudflare api" }
+            };
+
+            use log::error;
+            error!(target: "main", "{error_message}");
+            Err(error_message)
+        }
+    }
+}
+
+#[inline]
+pub(crate) async fn handle_record_delete(cloudflare_api: &CloudflareApi, record_id: &str) -> Result<(), &'static str> {
+    metrics::cloudflare("delete_record", cloudflare_api.delete_record(record_id).await).map(|_| ()).map_err(|error| {
+        use rest_api::cloudflare::error::Error;
+        let error_message = match error {
+            Error::Internal => { "Internal error caused due to invalid delete request sent to Cloudflare api" }
+            Error::Network => { "Network error occurred when deleting record value from Cloudflare api" }
+            Error::Unauthorized => { "Unauthorized responded when deleting record value from Cloudflare api" }
+            Error::InvalidZone => { "Invalid zone responded when deleting record value from Cloudflare api" }
+            Error::InvalidRecord => { "Invalid record id responded when deleting record value from Cloudflare api" }
+            Error::Server => { "Server error occurred when deleting record value from Cloudflare api" }
+            Error::DecodeResponse => { "Deserializing error occurred when processing record deletion response from Cloudflare api" }
+            Error::Unknown => { "Unknown error occurred when deleting record value from Cloudflare api" }
+        };
+
+        use log::error;
+        error!(target: "main", "{error_message}");
+        error_message
+    })
+}
+
+#[inline]
+async fn handle_record_create(cloudflare_api: &CloudflareApi, domain_name: &DomainName, ip: &String) -> Result<(), &'static str> {
+    let comment = match &domain_name.comment {
+        Some(template) => ownership::comment(template, domain_name, ip, state::now()),
+        None => ownership::MARKER.to_string()
+    };
+    use rest_api::cloudflare::record::NewRecord;
+    let new_record = NewRecord {
+        domain_name: &domain_name.name,
+        value: ip,
+        record_type: domain_name.domain_type.into(),
+        time_to_live: domain_name.time_to_live,
+        is_proxied: domain_name.proxied,
+        comment: Some(&comment),
+        tags: domain_name.tags.as_deref().unwrap_or_default()
+    };
+    let (domain_name, record_type) = (new_record.domain_name, new_record.record_type);
+
+    let result = cloudflare_api.create_record(&new_record).await;
+    match metrics::cloudflare("create_record", result) {
+        Ok(record) => {
+            if record.domain_name == *domain_name && record.record_type == record_type && record.value == *ip {
+                use log::info;
+                info!(target: "main", "Update {} ({}) record -> {}", record.domain_name, record.record_type, ip);
+                Ok(())
+            }
+            else {
+                use log::error;
+                error!(target: "main", "Failed to update {} ({})", record.domain_name, record.record_type);
+                Err("Unexpected record responded when creating record from Cloudflare api")
+            }
+        }
+        Err(error) => {
+            use rest_api::cloudflare::error::Error;
+            let error_message = match error {
+                Error::Internal => { "Internal error caused due to invalid creation request sent to Cloudflare api" }
+                Error::Network => { "Network error occurred when creating record value from Cloudflare api" }
+                Error::Unauthorized => { "Unauthorized responded when creating record value from Cloudflare api" }
+                Error::InvalidZone => { "Invalid zone responded when creating record value from Cloudflare api" }
+                Error::InvalidRecord => { "Invalid record id responded when creating record value from Cloudflare api" }
+                Error::Server => { "Server error occurred when creating record value from Cloudflare api" }
+                Error::DecodeResponse => { "Deserializing error occurred when processing record creation response from Cloudflare api" }
+                Error::Unknown => { "Unknown error occurred when creating record value from Cloudflare api" }
+            };
+
+            use log::error;
+            error!(target: "main", "{error_message}");
+            Err(error_message)
+        }
+    }
+}
\ No newline at end of file
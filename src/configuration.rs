@@ -1,35 +1,105 @@
 pub mod error;
 pub mod cloudflare;
 pub mod config;
+pub mod format;
 
 mod argument;
+mod check_config;
+mod document;
+mod environment;
+mod print_config;
 mod proxied;
+mod token;
+mod unknown_key;
+mod validate;
+
+use argument::Argument;
+use std::path::PathBuf;
 
 use error::Error;
 use cloudflare::Cloudflare;
 use config::Config;
+use format::Format;
 
 pub struct Configuration {
     pub cloudflare: Cloudflare,
     pub config: Config,
-    pub proxied: Option<bool>
+    pub proxied: Option<bool>,
+    pub print_config: Option<Format>,
+    pub daemon: bool,
+    /**
+     * Files the configuration was read from, watched for changes in daemon mode
+     **/
+    pub paths: Vec<PathBuf>
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
 
 impl Configuration {
     pub fn new() -> Result<Self> {
-        use argument::Argument;
         let arguments = Argument::all();
+        let configuration = Self::load(&arguments, None);
+
+        use check_config::GetCheckConfig;
+        if arguments.get_check_config() {
+            check_config::report(&configuration);
+        }
+        configuration
+    }
+
+    /**
+     * Load again through the same pipeline, a token read from stdin is carried over as stdin is consumed
+     **/
+    pub fn reload(&self) -> Result<Self> {
+        Self::load(&Argument::all(), Some(self))
+    }
 
-        use cloudflare::GetCloudflare;
-        use config::GetConfig;
+    fn load(arguments: &Vec<Argument>, previous: Option<&Self>) -> Result<Self> {
+        use document::{Document, GetDocument};
+        use print_config::GetPrintConfig;
         use proxied::GetProxied;
-        let (cloudflare, config, proxied) = (
-            arguments.get_cloudflare()?, arguments.get_config()?, arguments.get_proxied()
+        let (document, proxied, print_config) = (
+            arguments.get_document()?, arguments.get_proxied(), arguments.get_print_config()
         );
+        let Document { mut cloudflare, mut config, mut unknown_keys, mut paths } = document;
+
+        use environment::OverrideEnvironment;
+        cloudflare.override_environment(&mut unknown_keys)?;
+        config.override_environment(&mut unknown_keys)?;
+
+        let is_lenient = arguments.iter().any(|argument| matches!(argument, Argument::Lenient));
+        if !unknown_keys.is_empty() {
+            if config.strict && !is_lenient {
+                return Err(Error::UnknownKeys(unknown_keys));
+            }
+            use log::warn;
+            unknown_keys.iter().for_each(|unknown_key| warn!(target: "configuration", "Ignored {unknown_key}"));
+        }
+
+        use token::GetToken;
+        cloudflare.token = match previous {
+            Some(previous) if arguments.iter().any(|argument| matches!(argument, Argument::TokenStdin)) => {
+                previous.cloudflare.token.clone()
+            }
+            _ => arguments.get_token(&cloudflare)?
+        };
+        paths.extend(cloudflare.token_file.iter().cloned());
+        if cloudflare.zone.is_empty() {
+            return Err(Error::ZoneMissing);
+        }
+
+        use validate::Validate;
+        let mut issues = Vec::new();
+        cloudflare.validate("cloudflare", &mut issues);
+        config.validate("config", &mut issues);
+        validate::validate_references(&cloudflare, &config, &mut issues);
+        let daemon = arguments.iter().any(|argument| matches!(argument, Argument::Daemon));
+        validate::validate_persistence(&config, daemon, &mut issues);
+        if !issues.is_empty() {
+            return Err(Error::Invalid(issues));
+        }
 
-        let configuration = Self { cloudflare, config, proxied };
+        let configuration = Self { cloudflare, config, proxied, print_config, daemon, paths };
         Ok(configuration)
     }
 }
\ No newline at end of file
@@ -3,10 +3,17 @@ use std::{
     env
 };
 
+use super::format::Format;
+
 mod help;
+mod lenient;
+mod check_config;
 mod cloudflare_config;
 mod config;
+mod daemon;
+mod print_config;
 mod proxied;
+mod token_stdin;
 
 pub(super) enum Argument {
 
@@ -16,9 +23,19 @@ pub(super) enum Argument {
 
     CloudflareConfig(PathBuf),
 
+    TokenStdin,
+
+    PrintConfig(Format),
+
+    CheckConfig,
+
+    Lenient,
+
+    Daemon,
+
 }
 
-impl<'argument> Argument {
+impl Argument {
 
     pub(super) fn all() -> Vec<Self> {
         let mut vec = Vec::new();
@@ -40,6 +57,22 @@ impl<'argument> Argument {
                     use config::HandleConfig;
                     args.handle_config(&mut vec);
                 }
+                check_config::args::COMMAND | check_config::args::LONG => {
+                    vec.push(Argument::CheckConfig);
+                }
+                daemon::args::LONG | daemon::args::SHORT => {
+                    vec.push(Argument::Daemon);
+                }
+                lenient::args::LONG => {
+                    vec.push(Argument::Lenient);
+                }
+                print_config::args::LONG => {
+                    use print_config::HandlePrintConfig;
+                    args.handle_print_config(&mut vec);
+                }
+                token_stdin::args::LONG => {
+                    vec.push(Argument::TokenStdin);
+                }
                 // TODO: To be implemented
                 _ => {
                     // TODO: To be implemented
@@ -0,0 +1,4 @@
+pub(super) mod args {
+    pub const COMMAND: &str = "check-config";
+    pub const LONG: &str = "--check-config";
+}
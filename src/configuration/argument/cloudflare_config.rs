@@ -6,8 +6,8 @@ use std::{
 use super::Argument;
 
 pub(super) mod args {
-    pub const LONG: &'static str = "--cloudflare";
-    pub const SHORT: &'static str = "-cf";
+    pub const LONG: &str = "--cloudflare";
+    pub const SHORT: &str = "-cf";
 }
 
 pub(super) trait HandleCloudflareConfig {
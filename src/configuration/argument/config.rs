@@ -7,8 +7,8 @@ use super::Argument;
 
 pub(super) mod args {
 
-    pub const LONG: &'static str = "--config";
-    pub const SHORT: &'static str = "-c";
+    pub const LONG: &str = "--config";
+    pub const SHORT: &str = "-c";
 
 }
 
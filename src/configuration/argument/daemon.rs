@@ -0,0 +1,4 @@
+pub(super) mod args {
+    pub const LONG: &str = "--daemon";
+    pub const SHORT: &str = "-d";
+}
@@ -1,22 +1,65 @@
 pub(super) mod args {
 
-    pub const LONG: &'static str = "--help";
-    pub const SHORT: &'static str = "-h";
-    pub const SYMBOL: &'static str = "?";
+    pub const LONG: &str = "--help";
+    pub const SHORT: &str = "-h";
+    pub const SYMBOL: &str = "?";
 
 }
 
 fn message_str() -> String {
     String::from(
         concat!(
-            "Usage: cloudflare_dynamic_dns [options...] \n",
+            "Usage: cloudflare_dynamic_dns [check-config] [options...] \n",
+            "Commands: \n",
+            "  check-config, --check-config \n",
+            "      Load and validate the configuration, report any error with its location, then exit \n",
             "Options: \n",
             "  --help, -h, ? \n",
             "      Display all available command line options \n",
             "  --cloudflare, -cf <path> \n", 
-            "      Read cloudflare json configurations from <path> \n",
+            "      Read cloudflare configurations from <path>, overrides the `cloudflare` section of --config \n",
             "  --config, -c <path> \n",
-            "      Read configuration from <path> \n",
+            "      Read configuration from <path>, json, toml or yaml detected from the file extension \n",
+            "  --print-config [json|toml|yaml] \n",
+            "      Print the effective configuration with the token redacted, then exit \n",
+            "  --daemon, -d \n",
+            "      Keep running and check every `interval` seconds, reload configuration on SIGHUP or file change \n",
+            "  --lenient \n",
+            "      Warn about unknown configuration keys instead of rejecting them \n",
+            "  --token-stdin \n",
+            "      Read Cloudflare api token from standard input \n",
+            "\n",
+            "Environment: \n",
+            "  CLOUDFLARE_API_TOKEN \n",
+            "      Cloudflare api token, overrides `token` and `token-file` \n",
+            "  CFDDNS_CLOUDFLARE, CFDDNS_CONFIG \n",
+            "      Paths used when --cloudflare or --config is not specified \n",
+            "  CFDDNS_TOKEN, CFDDNS_TOKEN_FILE, CFDDNS_ZONE, CFDDNS_ZONE_NAME, CFDDNS_DOMAIN_NAMES, CFDDNS_PATTERNS \n",
+            "      Override the cloudflare configuration fields, CFDDNS_DOMAIN_NAMES and CFDDNS_PATTERNS take a json array \n",
+            "  CFDDNS_UNAVAILABLE_HIDE, CFDDNS_STRICT, CFDDNS_INTERVAL, CFDDNS_CLEANUP_REMOVED \n",
+            "      Override `unavailable-hide`, `strict`, `interval` and `cleanup-removed` \n",
+            "  CFDDNS_UNAVAILABLE_CHECKS, CFDDNS_UNAVAILABLE_GRACE, CFDDNS_UNAVAILABLE_ACTION, CFDDNS_STATE_FILE \n",
+            "      Override `unavailable-checks`, `unavailable-grace` in seconds, `unavailable-action` and `state-file` \n",
+            "  CFDDNS_STATUS_FILE, CFDDNS_STATUS_FORMAT \n",
+            "      Override `status-file` and `status-format`: `json` or `textfile` \n",
+            "  CFDDNS_CLOUDFLARE_API_URL, CFDDNS_IP_SB_URL_V4, CFDDNS_IP_SB_URL_V6 \n",
+            "      Override `cloudflare-api-url`, `ip-sb-url-v4` and `ip-sb-url-v6` \n",
+            "  CFDDNS_IP_SB_TIMEOUT_{TOTAL,CONNECT,READ}, CFDDNS_CLOUDFLARE_TIMEOUT_{TOTAL,CONNECT,READ} \n",
+            "      Override `ip-sb-timeout` and `cloudflare-timeout` in seconds \n",
+            "  CFDDNS_CLOUDFLARE_PROXY, CFDDNS_IP_SB_PROXY \n",
+            "      Override `cloudflare-proxy` and `ip-sb-proxy`: `environment`, `direct` or a http(s)/socks5(h) url \n",
+            "  CFDDNS_PROVIDERS \n",
+            "      Override `providers` with a json array \n",
+            "  CFDDNS_NOTIFICATIONS, CFDDNS_NOTIFY_FAILURES_AFTER \n",
+            "      Override `notifications` with a json array and `notify-failures-after` \n",
+            "  CFDDNS_SMTP, CFDDNS_SMTP_PASSWORD \n",
+            "      Override `smtp` with a json object and its `password` \n",
+            "  CFDDNS_PRE_CHANGE, CFDDNS_ON_CHANGE, CFDDNS_ON_ERROR, CFDDNS_HOOK_TIMEOUT \n",
+            "      Override the `pre-change`, `on-change` and `on-error` hook commands and `hook-timeout` in seconds \n",
+            "  CFDDNS_METRICS_LISTEN, CFDDNS_HEALTHZ_FAILURES \n",
+            "      Override `metrics-listen` and `healthz-failures` \n",
+            "  HTTPS_PROXY, HTTP_PROXY, ALL_PROXY, NO_PROXY \n",
+            "      Used by clients whose proxy is `environment`, NO_PROXY also applies to explicit proxy urls \n",
             "\n",
         )
     )
@@ -0,0 +1,3 @@
+pub(super) mod args {
+    pub const LONG: &str = "--lenient";
+}
@@ -0,0 +1,30 @@
+use std::{
+    iter::{Peekable, Skip},
+    env::Args
+};
+
+use super::{
+    super::format::Format,
+    Argument
+};
+
+pub(super) mod args {
+    pub const LONG: &str = "--print-config";
+}
+
+pub(super) trait HandlePrintConfig {
+    fn handle_print_config(&mut self, vec: &mut Vec<Argument>);
+}
+
+impl HandlePrintConfig for Peekable<Skip<Args>> {
+    fn handle_print_config(&mut self, vec: &mut Vec<Argument>) {
+        let format = self.peek()
+            .and_then(|name| Format::from_name(name))
+            .map_or(Format::Json, |format| {
+                // Do move iter into next, as the format name was recognized
+                self.next();
+                format
+            });
+        vec.push(Argument::PrintConfig(format));
+    }
+}
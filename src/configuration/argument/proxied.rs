@@ -7,8 +7,8 @@ use serde::Deserialize;
 use super::Argument;
 
 pub(super) mod args {
-    pub const LONG: &'static str = "--proxied";
-    pub const SHORT: &'static str = "-p";
+    pub const LONG: &str = "--proxied";
+    pub const SHORT: &str = "-p";
 }
 
 pub(super) trait HandleProxied {
@@ -27,7 +27,7 @@ impl HandleProxied for Peekable<Skip<Args>> {
     fn handle_proxied(&mut self, vec: &mut Vec<Argument>) {
         let is_proxied = self.peek()
             .and_then(String::deserialize_status)
-            .map_or(true, |status| {
+            .is_none_or(|status| {
                 // Do move iter into next, as it was deserialized successfully
                 self.next();
                 status.into()
@@ -36,9 +36,9 @@ impl HandleProxied for Peekable<Skip<Args>> {
     }
 }
 
-impl Into<bool> for Status {
-    fn into(self) -> bool {
-        match self {
+impl From<Status> for bool {
+    fn from(status: Status) -> Self {
+        match status {
             Status::On => { true }
             Status::Off => { false }
         }
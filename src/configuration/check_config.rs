@@ -0,0 +1,36 @@
+use super::{
+    argument::Argument,
+    Configuration,
+    Result
+};
+
+pub(super) trait GetCheckConfig {
+    fn get_check_config(&self) -> bool;
+}
+
+impl GetCheckConfig for Vec<Argument> {
+    fn get_check_config(&self) -> bool {
+        self.iter().any(|argument| matches!(argument, Argument::CheckConfig))
+    }
+}
+
+/**
+ * Report the outcome of loading and validating the configuration, then exit
+ * with 0 when it is usable and 1 otherwise.
+ **/
+pub(super) fn report(configuration: &Result<Configuration>) -> ! {
+    use std::process::exit;
+    match configuration {
+        Ok(configuration) => {
+            println!(
+                "Configuration is valid: zone {}, {} domain names",
+                configuration.cloudflare.zone, configuration.cloudflare.domain_names.len()
+            );
+            exit(0);
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            exit(1);
+        }
+    }
+}
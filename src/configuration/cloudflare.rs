@@ -1,58 +1,34 @@
-use serde::Deserialize;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize, Serializer};
 
 pub mod domain_name;
+pub mod pattern;
 
-use super::{
-    argument::Argument,
-    Result
-};
 use domain_name::DomainName;
+use pattern::Pattern;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize, Default)]
 pub struct Cloudflare {
+    #[serde(default, serialize_with = "redact")]
     pub token: String,
+    #[serde(rename = "token-file", alias = "token_file", default, skip_serializing_if = "Option::is_none")]
+    pub token_file: Option<PathBuf>,
+    #[serde(default)]
     pub zone: String,
-    #[serde(rename = "domain-names")]
+    /**
+     * Optional name of the zone, e.g. `example.com`, domain names are checked to be within it
+     **/
+    #[serde(rename = "zone-name", alias = "zone_name", default, skip_serializing_if = "Option::is_none")]
+    pub zone_name: Option<String>,
+    #[serde(rename = "domain-names", alias = "domain_names", default)]
     pub domain_names: Vec<DomainName>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<Pattern>
 }
 
-impl<'cloudflare> Cloudflare {
-    const DEFAULT_PATH: &'cloudflare str = "/usr/local/etc/cloudflare_dynamic_dns/cloudflare.conf.json";
+/**
+ * Never let the token leave through `--print-config`
+ **/
+fn redact<S: Serializer>(token: &str, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(if token.is_empty() { "" } else { "<redacted>" })
 }
-
-pub(super) trait GetCloudflare {
-    fn get_cloudflare(&self) -> Result<Cloudflare>;
-}
-
-impl GetCloudflare for Vec<Argument> {
-    fn get_cloudflare(&self) -> Result<Cloudflare> {
-        let path_buf = self.iter()
-            .find_map(|argument|
-                if let Argument::CloudflareConfig(path_buf) = argument { Some(path_buf.to_owned()) }
-                else { None }
-            )
-            .unwrap_or_else(|| {
-                use log::info;
-                info!(
-                    target: "configuration.cloudflare",
-                    r#"Cloudflare json configuration file not specified: Use default path "{}" instead."#,
-                    Cloudflare::DEFAULT_PATH
-                );
-                use std::path::PathBuf;
-                PathBuf::from(Cloudflare::DEFAULT_PATH)
-            });
-
-        use std::fs::read_to_string;
-        let cloudflare_config_json_str = read_to_string(&path_buf)
-            .map_err(|_| {
-                use super::error::Error;
-                Error::CloudflareImportFail(path_buf.to_string_lossy().to_string())
-            })?;
-
-        serde_json::from_str(&cloudflare_config_json_str)
-            .map_err(|_| {
-                use super::error::Error;
-                Error::CloudflareImportFail(path_buf.to_string_lossy().to_string())
-            })
-    }
-}
\ No newline at end of file
@@ -1,33 +1,105 @@
-use serde::Deserialize;
+use std::{
+    fmt::{Display, Formatter},
+    net::{IpAddr, Ipv6Addr}
+};
+use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize)]
+pub mod source;
+
+use source::Source;
+
+#[derive(Deserialize, Serialize, Clone)]
 pub struct DomainName {
     pub name: String,
-    #[serde(rename="domain-type", default)]
+    #[serde(rename = "domain-type", alias = "domain_type", default)]
     pub domain_type: DomainType,
     #[serde(default = "default::proxied")]
     pub proxied: bool,
-    #[serde(default = "default::time_to_live")]
-    pub time_to_live: u16
+    #[serde(rename = "time-to-live", alias = "time_to_live", default = "default::time_to_live")]
+    pub time_to_live: u32,
+    /**
+     * Where the published address comes from, the default detection when absent
+     **/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+    /**
+     * Shorthand of `source: { provider: ... }`
+     **/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    /**
+     * Interface identifier of another host, e.g. `::1234:5678:9abc:def0`,
+     * published behind the /64 prefix of the detected ipv6 address so the record follows prefix changes
+     **/
+    #[serde(rename = "ipv6-suffix", alias = "ipv6_suffix", alias = "ipv6-host-id", alias = "ipv6_host_id", default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_suffix: Option<Ipv6Addr>,
+    /**
+     * Take over an existing record without the ownership marker, which is otherwise never overwritten or deleted
+     **/
+    #[serde(default)]
+    pub adopt: bool,
+    /**
+     * Comment of the record with `{name}`, `{address}`, `{provider}` and `{time}` replaced,
+     * the ownership marker is always appended. A comment with `{time}` is only rewritten along with the address.
+     * Rendered, it must fit the 100 characters free plans take or the 500 of paid plans.
+     **/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    /**
+     * Tags of the record, left as they are when absent
+     **/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tags: Option<Vec<String>>,
+    /**
+     * Expanded from `patterns`, such a record is neither created nor deleted
+     **/
+    #[serde(skip)]
+    pub pattern: bool
+}
+
+impl DomainName {
+    pub fn effective_source(&self) -> Source {
+        match (&self.source, &self.provider) {
+            (Some(source), _) => source.clone(),
+            (None, Some(provider)) => Source::Provider(provider.clone()),
+            (None, None) => Source::Wan
+        }
+    }
 }
 
-#[derive(Deserialize, Default, Clone, Copy)]
+#[derive(Deserialize, Serialize, Default, Clone, Copy, PartialEq, Debug)]
+#[allow(clippy::upper_case_acronyms)]
 pub enum DomainType {
     #[default]
     A,
     AAAA
 }
 
+impl DomainType {
+    pub fn accepts(&self, address: &IpAddr) -> bool {
+        matches!((self, address), (DomainType::A, IpAddr::V4(_)) | (DomainType::AAAA, IpAddr::V6(_)))
+    }
+}
+
+impl Display for DomainType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DomainType::A => f.write_str("A"),
+            DomainType::AAAA => f.write_str("AAAA")
+        }
+    }
+}
+
 mod default {
 
     /**
      * Let default as `auto`, see https://developers.cloudflare.com/api/resources/dns/subresources/records/models/ttl/#(schema)
-     * ```
+     * ```text
      * Time To Live (TTL) of the DNS record in seconds. Setting to 1 means 'automatic'.
      * Value must be between 60 and 86400, with the minimum reduced to 30 for Enterprise zones.
      * ```
      **/
-    pub(super) fn time_to_live() -> u16 { 1 }
+    pub(super) fn time_to_live() -> u32 { 1 }
 
     pub(super) fn proxied() -> bool { true }
 
@@ -0,0 +1,113 @@
+use std::{
+    fmt::{Display, Formatter},
+    net::IpAddr,
+    path::PathBuf
+};
+use serde::{Deserialize, Serialize};
+
+/**
+ * Where the address published by a domain name comes from, e.g. `"wan"`, `{ "provider": "wan2" }`,
+ * `{ "interface": "br-lan" }`, `{ "delegated-prefix": "/tmp/odhcp6c.env" }` or `{ "static": "192.0.2.10" }`
+ **/
+#[derive(Deserialize, Serialize, Clone, PartialEq, Debug)]
+#[serde(from = "Representation", into = "Representation")]
+pub enum Source {
+    /**
+     * The default detection through `ip-sb-url-v4` and `ip-sb-url-v6`
+     **/
+    Wan,
+    /**
+     * A named detection declared in `config.providers`
+     **/
+    Provider(String),
+    /**
+     * Addresses assigned to a local interface
+     **/
+    Interface(String),
+    /**
+     * The prefix delegated by DHCPv6-PD as written to a lease file, combined with `ipv6-suffix`
+     **/
+    DelegatedPrefix(PathBuf),
+    Static(IpAddr)
+}
+
+impl Display for Source {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Wan => f.write_str("wan"),
+            Source::Provider(provider) => write!(f, "provider {provider}"),
+            Source::Interface(interface) => write!(f, "interface {interface}"),
+            Source::DelegatedPrefix(lease) => write!(f, "delegated prefix {}", lease.display()),
+            Source::Static(address) => write!(f, "static {address}")
+        }
+    }
+}
+
+/**
+ * Single key maps rather than externally tagged enum, which serde_yaml expects as `!static` tags
+ **/
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum Representation {
+    Keyword(Keyword),
+    Provider { provider: String },
+    Interface { interface: String },
+    DelegatedPrefix {
+        #[serde(rename = "delegated-prefix")]
+        lease: PathBuf
+    },
+    Static {
+        #[serde(rename = "static")]
+        address: IpAddr
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+enum Keyword {
+    Wan
+}
+
+impl From<Representation> for Source {
+    fn from(representation: Representation) -> Self {
+        match representation {
+            Representation::Keyword(Keyword::Wan) => Source::Wan,
+            Representation::Provider { provider } => Source::Provider(provider),
+            Representation::Interface { interface } => Source::Interface(interface),
+            Representation::DelegatedPrefix { lease } => Source::DelegatedPrefix(lease),
+            Representation::Static { address } => Source::Static(address)
+        }
+    }
+}
+
+impl From<Source> for Representation {
+    fn from(source: Source) -> Self {
+        match source {
+            Source::Wan => Representation::Keyword(Keyword::Wan),
+            Source::Provider(provider) => Representation::Provider { provider },
+            Source::Interface(interface) => Representation::Interface { interface },
+            Source::DelegatedPrefix(lease) => Representation::DelegatedPrefix { lease },
+            Source::Static(address) => Representation::Static { address }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::super::super::format::Format, Source};
+
+    #[test]
+    fn test_source_formats() {
+        assert_eq!(Format::Yaml.deserialize::<Source>("{ interface: br-lan }").unwrap(), Source::Interface(String::from("br-lan")));
+        assert_eq!(Format::Json.deserialize::<Source>(r#""wan""#).unwrap(), Source::Wan);
+        let source = Source::Static("192.0.2.10".parse().unwrap());
+        assert_eq!(serde_json::to_string(&source).unwrap(), r#"{"static":"192.0.2.10"}"#);
+        assert_eq!(Format::Toml.deserialize::<Wrapper>(r#"source = { static = "192.0.2.10" }"#).unwrap().source, source);
+        assert!(Format::Json.deserialize::<Source>(r#"{ "static": "home" }"#).is_err());
+    }
+
+    #[derive(serde::Deserialize)]
+    struct Wrapper {
+        source: Source
+    }
+}
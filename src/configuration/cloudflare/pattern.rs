@@ -0,0 +1,101 @@
+use std::net::Ipv6Addr;
+use serde::{Deserialize, Serialize};
+
+use super::domain_name::{source::Source, DomainName, DomainType};
+
+/**
+ * Existing records of the zone kept at the current address, matched by `name`, `tag` or both.
+ * Matched records are never created nor deleted, only their address is updated.
+ **/
+#[derive(Deserialize, Serialize)]
+pub struct Pattern {
+    /**
+     * Name where `*` stands for any characters, e.g. `*.home.example.com` matches every name below `home.example.com`
+     **/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /**
+     * Tag a record carries, either exactly or as the name of a `name:value` tag
+     **/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+    #[serde(rename = "domain-type", alias = "domain_type", default)]
+    pub domain_type: DomainType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<Source>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(rename = "ipv6-suffix", alias = "ipv6_suffix", alias = "ipv6-host-id", alias = "ipv6_host_id", default, skip_serializing_if = "Option::is_none")]
+    pub ipv6_suffix: Option<Ipv6Addr>,
+    #[serde(default)]
+    pub adopt: bool
+}
+
+impl Pattern {
+    pub fn matches(&self, name: &str, domain_type: DomainType, tags: &[String]) -> bool {
+        domain_type == self.domain_type
+            && self.name.as_deref().is_none_or(|pattern| glob(&pattern.to_ascii_lowercase(), &name.to_ascii_lowercase()))
+            && self.tag.as_deref().is_none_or(|tag|
+                tags.iter().any(|carried| carried == tag || carried.split_once(':').is_some_and(|(carried, _)| carried == tag))
+            )
+    }
+
+    /**
+     * The domain name a record named `name` is handled as, keeping its `proxied` and `time_to_live`
+     **/
+    pub fn domain_name(&self, name: &str, proxied: bool, time_to_live: u32) -> DomainName {
+        DomainName {
+            name: name.to_string(),
+            domain_type: self.domain_type,
+            proxied,
+            time_to_live,
+            source: self.source.clone(),
+            provider: self.provider.clone(),
+            ipv6_suffix: self.ipv6_suffix,
+            adopt: self.adopt,
+            comment: None,
+            tags: None,
+            pattern: true
+        }
+    }
+}
+
+/**
+ * Whether `name` matches `pattern`, `*` matching any run of characters dots included
+ **/
+fn glob(pattern: &str, name: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == name,
+        Some((prefix, rest)) => name.strip_prefix(prefix).is_some_and(|name|
+            (0..=name.len()).filter(|index| name.is_char_boundary(*index)).any(|index| glob(rest, &name[index..]))
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{glob, DomainType, Pattern};
+
+    #[test]
+    fn test_matches() {
+        assert!(glob("*.home.example.com", "a.home.example.com"));
+        assert!(glob("*.home.example.com", "a.b.home.example.com"));
+        assert!(glob("*.home.example.com", "*.home.example.com"));
+        assert!(!glob("*.home.example.com", "home.example.com"));
+        assert!(glob("host-*.example.com", "host-1.example.com"));
+
+        let pattern = Pattern {
+            name: None,
+            tag: Some(String::from("ddns")),
+            domain_type: DomainType::A,
+            source: None,
+            provider: None,
+            ipv6_suffix: None,
+            adopt: false
+        };
+        assert!(pattern.matches("a.example.com", DomainType::A, &[String::from("ddns")]));
+        assert!(pattern.matches("a.example.com", DomainType::A, &[String::from("ddns:home")]));
+        assert!(!pattern.matches("a.example.com", DomainType::AAAA, &[String::from("ddns")]));
+        assert!(!pattern.matches("a.example.com", DomainType::A, &[String::from("ddnsx")]));
+    }
+}
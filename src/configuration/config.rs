@@ -1,52 +1,205 @@
-use serde::Deserialize;
+use std::{net::SocketAddr, path::PathBuf};
+use serde::{Deserialize, Serialize, Serializer};
 
-use super::{
-    argument::Argument,
-    Result
-};
+use crate::rest_api::Proxy;
 
+pub mod notification;
+pub mod provider;
+pub mod smtp;
+pub mod status;
 mod timeout;
+pub mod unavailable;
+
+use notification::Notification;
+use provider::Provider;
+use smtp::Smtp;
+use status::StatusFormat;
 use timeout::Timeout;
+use unavailable::{Park, UnavailableAction};
 
-#[derive(Deserialize, Default)]
+#[derive(Deserialize, Serialize)]
 pub struct Config {
-    #[serde(rename = "unavailable-hide", default = "default::unavailable_hide")]
+    #[serde(rename = "unavailable-hide", alias = "unavailable_hide", default = "default::unavailable_hide")]
     pub unavailable_hide: bool,
-    #[serde(rename = "ip.sb-timeout", default)]
+    /**
+     * Consecutive checks an address family must be missing before `unavailable-action` is taken
+     **/
+    #[serde(rename = "unavailable-checks", alias = "unavailable_checks", default = "default::unavailable_checks")]
+    pub unavailable_checks: u32,
+    /**
+     * Seconds an address family must be missing before `unavailable-action` is taken
+     **/
+    #[serde(rename = "unavailable-grace", alias = "unavailable_grace", default)]
+    pub unavailable_grace: u64,
+    #[serde(rename = "unavailable-action", alias = "unavailable_action", default)]
+    pub unavailable_action: UnavailableAction,
+    #[serde(default)]
+    pub park: Park,
+    /**
+     * Where missing address families are tracked across runs, in memory only when absent,
+     * which leaves `unavailable-checks` and `unavailable-grace` to daemon mode
+     **/
+    #[serde(rename = "state-file", alias = "state_file", default, skip_serializing_if = "Option::is_none")]
+    pub state_file: Option<PathBuf>,
+    /**
+     * Outcome of the last check, rewritten after every check
+     **/
+    #[serde(rename = "status-file", alias = "status_file", default, skip_serializing_if = "Option::is_none")]
+    pub status_file: Option<PathBuf>,
+    #[serde(rename = "status-format", alias = "status_format", default)]
+    pub status_format: StatusFormat,
+    // Printed as `ip-sb-timeout`, a dotted key would be split into nested tables by toml
+    #[serde(rename = "ip-sb-timeout", alias = "ip.sb-timeout", alias = "ip_sb_timeout", default)]
     pub ip_sb_timeout: Timeout,
-    #[serde(rename = "cloudflare-timeout", default)]
-    pub cloudflare_timeout: Timeout
+    #[serde(rename = "cloudflare-timeout", alias = "cloudflare_timeout", default)]
+    pub cloudflare_timeout: Timeout,
+    /**
+     * Reject unknown keys, disable to keep older versions running on newer configuration files
+     **/
+    #[serde(default = "default::strict")]
+    pub strict: bool,
+    /**
+     * Seconds between two checks in daemon mode
+     **/
+    #[serde(default = "default::interval")]
+    pub interval: u64,
+    /**
+     * Delete records of domain names removed from the configuration on reload in daemon mode
+     **/
+    #[serde(rename = "cleanup-removed", alias = "cleanup_removed", default)]
+    pub cleanup_removed: bool,
+    /**
+     * Root of the Cloudflare api, `https://api.cloudflare.com/client/v4` when absent
+     **/
+    #[serde(rename = "cloudflare-api-url", alias = "cloudflare_api_url", default, skip_serializing_if = "Option::is_none")]
+    pub cloudflare_api_url: Option<String>,
+    /**
+     * Endpoints responding the ipv4 and ipv6 address, ip.sb when absent.
+     * Any echo service responding the bare address or `{"ip": "..."}` works, both may be the same dual-stack url.
+     **/
+    #[serde(rename = "ip-sb-url-v4", alias = "ip_sb_url_v4", default, skip_serializing_if = "Option::is_none")]
+    pub ip_sb_url_v4: Option<String>,
+    #[serde(rename = "ip-sb-url-v6", alias = "ip_sb_url_v6", default, skip_serializing_if = "Option::is_none")]
+    pub ip_sb_url_v6: Option<String>,
+    /**
+     * `environment` by default, honouring `HTTPS_PROXY` and `NO_PROXY`, `direct` opts out
+     **/
+    #[serde(rename = "cloudflare-proxy", alias = "cloudflare_proxy", default = "default::cloudflare_proxy", serialize_with = "redact")]
+    pub cloudflare_proxy: Proxy,
+    /**
+     * `direct` by default, ip detection through a proxy detects the address of the proxy
+     **/
+    #[serde(rename = "ip-sb-proxy", alias = "ip_sb_proxy", default = "default::ip_sb_proxy", serialize_with = "redact")]
+    pub ip_sb_proxy: Proxy,
+    /**
+     * Named ip detections bound to an interface or local address, one per uplink
+     **/
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub providers: Vec<Provider>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notifications: Vec<Notification>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smtp: Option<Smtp>,
+    /**
+     * Consecutive failing checks before a failure is notified, once until recovered
+     **/
+    #[serde(rename = "notify-failures-after", alias = "notify_failures_after", default = "default::notify_failures_after")]
+    pub notify_failures_after: u32,
+    /**
+     * Shell command run before every record change, a non-zero exit vetoes the change
+     **/
+    #[serde(rename = "pre-change", alias = "pre_change", default, skip_serializing_if = "Option::is_none")]
+    pub pre_change: Option<String>,
+    /**
+     * Shell command run after every record change, with `CFDDNS_ACTION`, `CFDDNS_RECORD`, `CFDDNS_TYPE`,
+     * `CFDDNS_OLD_IPV4`, `CFDDNS_NEW_IPV4`, `CFDDNS_OLD_IPV6` and `CFDDNS_NEW_IPV6` set as applicable.
+     * A `modify` of proxied state, comment, tags or ttl sets `CFDDNS_OLD` and `CFDDNS_NEW` like `proxied=false` instead.
+     **/
+    #[serde(rename = "on-change", alias = "on_change", default, skip_serializing_if = "Option::is_none")]
+    pub on_change: Option<String>,
+    /**
+     * Shell command run after a failing check, with `CFDDNS_ERROR` set
+     **/
+    #[serde(rename = "on-error", alias = "on_error", default, skip_serializing_if = "Option::is_none")]
+    pub on_error: Option<String>,
+    /**
+     * Seconds a hook may run before it is killed
+     **/
+    #[serde(rename = "hook-timeout", alias = "hook_timeout", default = "default::hook_timeout")]
+    pub hook_timeout: u64,
+    /**
+     * Address serving `/metrics` and `/healthz` in daemon mode, e.g. `127.0.0.1:9101`
+     **/
+    #[serde(rename = "metrics-listen", alias = "metrics_listen", default, skip_serializing_if = "Option::is_none")]
+    pub metrics_listen: Option<SocketAddr>,
+    /**
+     * Consecutive failing checks before `/healthz` fails
+     **/
+    #[serde(rename = "healthz-failures", alias = "healthz_failures", default = "default::healthz_failures")]
+    pub healthz_failures: u32
     // TODO: Non-finalized declaration, leave for future needed
 }
 
 mod default {
+    use crate::rest_api::Proxy;
+
     pub(super) fn unavailable_hide() -> bool { true }
-}
 
-pub(super) trait GetConfig {
-    fn get_config(&self) -> Result<Config>;
+    pub(super) fn unavailable_checks() -> u32 { 1 }
+
+    pub(super) fn strict() -> bool { true }
+
+    pub(super) fn interval() -> u64 { 300 }
+
+    pub(super) fn notify_failures_after() -> u32 { 1 }
+
+    pub(super) fn hook_timeout() -> u64 { 30 }
+
+    pub(super) fn healthz_failures() -> u32 { 3 }
+
+    pub(super) fn cloudflare_proxy() -> Proxy { Proxy::Environment }
+
+    pub(super) fn ip_sb_proxy() -> Proxy { Proxy::Direct }
 }
 
-impl GetConfig for Vec<Argument> {
-    fn get_config(&self) -> Result<Config> {
-        let path_buf = self.iter()
-            .find_map(|argument| if let Argument::Config(path_buf) = argument { Some(path_buf) } else { None });
-
-        if let Some(path_buf) = path_buf {
-            use std::fs::read_to_string;
-            let config_json_str = read_to_string(&path_buf).map_err(|_| {
-                use super::error::Error;
-                Error::ConfigImportFail(path_buf.to_string_lossy().to_string())
-            })?;
-
-            serde_json::from_str(&config_json_str)
-                .map_err(|_| {
-                    use super::error::Error;
-                    Error::ConfigImportFail(path_buf.to_string_lossy().to_string())
-                })
-        }
-        else {
-            Ok(Config::default())
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            unavailable_hide: default::unavailable_hide(),
+            unavailable_checks: default::unavailable_checks(),
+            unavailable_grace: 0,
+            unavailable_action: UnavailableAction::default(),
+            park: Park::default(),
+            state_file: None,
+            status_file: None,
+            status_format: StatusFormat::default(),
+            ip_sb_timeout: Timeout::default(),
+            cloudflare_timeout: Timeout::default(),
+            strict: default::strict(),
+            interval: default::interval(),
+            cleanup_removed: false,
+            cloudflare_api_url: None,
+            ip_sb_url_v4: None,
+            ip_sb_url_v6: None,
+            cloudflare_proxy: default::cloudflare_proxy(),
+            ip_sb_proxy: default::ip_sb_proxy(),
+            providers: Vec::new(),
+            notifications: Vec::new(),
+            smtp: None,
+            notify_failures_after: default::notify_failures_after(),
+            pre_change: None,
+            on_change: None,
+            on_error: None,
+            hook_timeout: default::hook_timeout(),
+            metrics_listen: None,
+            healthz_failures: default::healthz_failures()
         }
     }
-}
\ No newline at end of file
+}
+
+/**
+ * Proxy urls may carry credentials, never let them leave through `--print-config`
+ **/
+fn redact<S: Serializer>(proxy: &Proxy, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str(&proxy.to_redacted_string())
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize, Serializer};
+
+/**
+ * A webhook notified of record changes and failures
+ **/
+#[derive(Deserialize, Serialize)]
+pub struct Notification {
+    #[serde(default)]
+    pub preset: Preset,
+    /**
+     * Webhooks carry their secret in the url, only scheme and host are printed
+     **/
+    #[serde(serialize_with = "redact_url")]
+    pub url: String,
+    /**
+     * Chat the Telegram bot posts to
+     **/
+    #[serde(rename = "chat-id", alias = "chat_id", default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<String>,
+    /**
+     * Events to notify, all when empty
+     **/
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<EventKind>,
+    /**
+     * Body of a generic webhook or message of a preset, with `{event}`, `{name}`, `{type}`, `{old}`, `{new}`,
+     * `{message}` and `{text}` replaced. Values are json escaped in the body of a generic webhook.
+     **/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty", serialize_with = "redact_headers")]
+    pub headers: BTreeMap<String, String>
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum Preset {
+    /**
+     * Json post of every field, or `template`
+     **/
+    #[default]
+    Generic,
+    Slack,
+    Discord,
+    /**
+     * `https://api.telegram.org/bot<token>/sendMessage` with `chat-id`
+     **/
+    Telegram,
+    /**
+     * Topic url such as `https://ntfy.sh/<topic>`
+     **/
+    Ntfy,
+    /**
+     * `https://<server>/message?token=<token>`
+     **/
+    Gotify
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum EventKind {
+    Created,
+    Changed,
+    Deleted,
+    Failed,
+    Recovered
+}
+
+fn redact_url<S: Serializer>(url: &str, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    use reqwest::Url;
+    match Url::parse(url) {
+        Ok(url) => serializer.serialize_str(&format!("{}://{}/<redacted>", url.scheme(), url.host_str().unwrap_or_default())),
+        Err(_) => serializer.serialize_str("<redacted>")
+    }
+}
+
+fn redact_headers<S: Serializer>(headers: &BTreeMap<String, String>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    use serde::ser::SerializeMap;
+    let mut map = serializer.serialize_map(Some(headers.len()))?;
+    for name in headers.keys() {
+        map.serialize_entry(name, "<redacted>")?;
+    }
+    map.end()
+}
@@ -0,0 +1,29 @@
+use std::net::IpAddr;
+use serde::{Deserialize, Serialize};
+
+use crate::rest_api::Binding;
+
+/**
+ * A named ip detection, domain names refer to it by `provider` to publish the address of one uplink
+ **/
+#[derive(Deserialize, Serialize)]
+pub struct Provider {
+    pub name: String,
+    /**
+     * Endpoints of this provider, `ip-sb-url-v4` and `ip-sb-url-v6` when absent
+     **/
+    #[serde(rename = "url-v4", alias = "url_v4", default, skip_serializing_if = "Option::is_none")]
+    pub url_v4: Option<String>,
+    #[serde(rename = "url-v6", alias = "url_v6", default, skip_serializing_if = "Option::is_none")]
+    pub url_v6: Option<String>,
+    #[serde(rename = "bind-interface", alias = "bind_interface", default, skip_serializing_if = "Option::is_none")]
+    pub bind_interface: Option<String>,
+    #[serde(rename = "local-address", alias = "local_address", default, skip_serializing_if = "Option::is_none")]
+    pub local_address: Option<IpAddr>
+}
+
+impl Provider {
+    pub fn binding(&self) -> Binding {
+        Binding { interface: self.bind_interface.clone(), local_address: self.local_address }
+    }
+}
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize, Serializer};
+
+use super::notification::EventKind;
+
+/**
+ * A mail server notified of the same events as `notifications`, one summary message per check
+ **/
+#[derive(Deserialize, Serialize)]
+pub struct Smtp {
+    pub host: String,
+    /**
+     * 587 for `starttls`, 465 for `tls` and 25 for `none` when absent
+     **/
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(default)]
+    pub security: Security,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none", serialize_with = "redact")]
+    pub password: Option<String>,
+    pub from: String,
+    pub to: Vec<String>,
+    /**
+     * Events to mail, all when empty
+     **/
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<EventKind>,
+    /**
+     * Seconds a delivery may take, independent of the Cloudflare api timeouts
+     **/
+    #[serde(default = "default::timeout")]
+    pub timeout: u64
+}
+
+mod default {
+    use crate::rest_api::builder::DEFAULT_TOTAL_TIMEOUT;
+
+    pub(super) fn timeout() -> u64 { DEFAULT_TOTAL_TIMEOUT.as_secs() }
+
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum Security {
+    #[default]
+    Starttls,
+    /**
+     * Implicit tls from the first byte
+     **/
+    Tls,
+    /**
+     * Plain text, only meant for a relay on the same host
+     **/
+    None
+}
+
+impl Smtp {
+    pub fn port(&self) -> u16 {
+        self.port.unwrap_or(match self.security {
+            Security::Starttls => 587,
+            Security::Tls => 465,
+            Security::None => 25
+        })
+    }
+}
+
+fn redact<S: Serializer>(_: &Option<String>, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+    serializer.serialize_str("<redacted>")
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum StatusFormat {
+    #[default]
+    Json,
+    /**
+     * Prometheus text format for the node_exporter textfile collector
+     **/
+    Textfile
+}
+
+impl std::str::FromStr for StatusFormat {
+    type Err = ();
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "textfile" => Ok(Self::Textfile),
+            _ => Err(())
+        }
+    }
+}
@@ -1,9 +1,12 @@
-use serde::Deserialize;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+
+use super::super::Result;
 
 /**
  * For default timeout, see [Hypertext Transfer Protocol (HTTP) Timeouts](https://www.ietf.org/archive/id/draft-thomson-hybi-http-timeout-00.html#rfc.section.1.1)
  **/
-#[derive(Deserialize)]
+#[derive(Deserialize, Serialize)]
 pub struct Timeout {
     #[serde(default = "default::total")]
     total: u64,
@@ -14,12 +17,13 @@ pub struct Timeout {
 }
 
 mod default {
+    use crate::rest_api::builder::{DEFAULT_CONNECT_TIMEOUT, DEFAULT_READ_TIMEOUT, DEFAULT_TOTAL_TIMEOUT};
 
-    pub(super) fn total() -> u64 { 300 }
+    pub(super) fn total() -> u64 { DEFAULT_TOTAL_TIMEOUT.as_secs() }
 
-    pub(super) fn connect() -> u64 { 120 }
+    pub(super) fn connect() -> u64 { DEFAULT_CONNECT_TIMEOUT.as_secs() }
 
-    pub(super) fn read() -> u64 { 30 }
+    pub(super) fn read() -> u64 { DEFAULT_READ_TIMEOUT.as_secs() }
 
 }
 
@@ -27,6 +31,27 @@ impl Timeout {
     pub fn all(&self) -> (u64, u64, u64) { 
         (self.total, self.connect, self.read) 
     }
+
+    pub fn durations(&self) -> (Duration, Duration, Duration) {
+        (Duration::from_secs(self.total), Duration::from_secs(self.connect), Duration::from_secs(self.read))
+    }
+
+    /**
+     * Apply `{prefix}_TOTAL`, `{prefix}_CONNECT` and `{prefix}_READ` when present
+     **/
+    pub(in crate::configuration) fn override_environment(&mut self, prefix: &str) -> Result<()> {
+        use super::super::environment::parse;
+        if let Some(total) = parse(&format!("{prefix}_TOTAL"))? {
+            self.total = total;
+        }
+        if let Some(connect) = parse(&format!("{prefix}_CONNECT"))? {
+            self.connect = connect;
+        }
+        if let Some(read) = parse(&format!("{prefix}_READ"))? {
+            self.read = read;
+        }
+        Ok(())
+    }
 }
 
 impl Default for Timeout {
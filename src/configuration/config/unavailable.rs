@@ -0,0 +1,40 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+use serde::{Deserialize, Serialize};
+
+/**
+ * What happens to a record once its address family stayed missing past the grace period
+ **/
+#[derive(Deserialize, Serialize, Clone, Copy, PartialEq, Default, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum UnavailableAction {
+    #[default]
+    Delete,
+    /**
+     * Point the record at `park.v4` or `park.v6` instead, keeping its settings for when the family returns
+     **/
+    Park
+}
+
+impl std::str::FromStr for UnavailableAction {
+    type Err = ();
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
+        match str.to_ascii_lowercase().as_str() {
+            "delete" => Ok(Self::Delete),
+            "park" => Ok(Self::Park),
+            _ => Err(())
+        }
+    }
+}
+
+/**
+ * Placeholders of parked records
+ **/
+#[derive(Deserialize, Serialize, Default)]
+pub struct Park {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v4: Option<Ipv4Addr>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v6: Option<Ipv6Addr>
+}
+
@@ -0,0 +1,210 @@
+use std::path::{Path, PathBuf};
+use serde::Deserialize;
+
+use super::{
+    argument::Argument,
+    cloudflare::Cloudflare,
+    config::Config,
+    format::Format,
+    unknown_key::{unknown_key, UnknownKey},
+    Result
+};
+
+/**
+ * Both sections of the configuration, in whichever layout they were found:
+ * ```json
+ * { "cloudflare": { "token": ..., "zone": ..., "domain-names": [...] }, "config": { "unavailable-hide": ... } }
+ * ```
+ * The split layout, `--cloudflare` for the first section and `--config` for the second, is still accepted.
+ **/
+pub(super) struct Document {
+    pub cloudflare: Cloudflare,
+    pub config: Config,
+    pub unknown_keys: Vec<UnknownKey>,
+    pub paths: Vec<PathBuf>
+}
+
+#[derive(Deserialize)]
+pub(super) struct Unified {
+    cloudflare: Option<Cloudflare>,
+    #[serde(default)]
+    config: Config
+}
+
+impl Document {
+    const DEFAULT_DIRECTORY: &str = "/usr/local/etc/cloudflare_dynamic_dns";
+    const DEFAULT_NAMES: [&str; 4] = ["config.json", "config.toml", "config.yaml", "config.yml"];
+    const LEGACY_CLOUDFLARE_PATH: &str = "/usr/local/etc/cloudflare_dynamic_dns/cloudflare.conf.json";
+}
+
+pub(super) trait GetDocument {
+    fn get_document(&self) -> Result<Document>;
+}
+
+impl GetDocument for Vec<Argument> {
+    fn get_document(&self) -> Result<Document> {
+        use super::environment::{var, variable};
+        let config_path = self.iter()
+            .find_map(|argument| if let Argument::Config(path_buf) = argument { Some(path_buf.to_owned()) } else { None })
+            .or_else(|| var(variable::CONFIG).map(PathBuf::from))
+            .or_else(default_config_path);
+        let cloudflare_path = self.iter()
+            .find_map(|argument|
+                if let Argument::CloudflareConfig(path_buf) = argument { Some(path_buf.to_owned()) }
+                else { None }
+            )
+            .or_else(|| var(variable::CLOUDFLARE).map(PathBuf::from));
+
+        let mut unknown_keys = Vec::new();
+        let mut paths = config_path.iter().cloned().collect::<Vec<_>>();
+        let (section, config) = match &config_path {
+            Some(path_buf) => read_config(path_buf, &mut unknown_keys)?,
+            None => (None, Config::default())
+        };
+
+        let cloudflare = match (cloudflare_path, section) {
+            (Some(path_buf), section) => {
+                if section.is_some() {
+                    use log::warn;
+                    warn!(
+                        target: "configuration.document",
+                        "Cloudflare section of {} is overridden by {}",
+                        config_path.as_deref().map(Path::display).map(|path| path.to_string()).unwrap_or_default(),
+                        path_buf.display()
+                    );
+                }
+                let cloudflare = read_cloudflare(&path_buf, &mut unknown_keys)?;
+                paths.push(path_buf);
+                cloudflare
+            }
+            (None, Some(section)) => section,
+            (None, None) => {
+                use log::info;
+                info!(
+                    target: "configuration.document",
+                    r#"Cloudflare configuration not specified: Use default path "{}" instead."#,
+                    Document::LEGACY_CLOUDFLARE_PATH
+                );
+                let path_buf = PathBuf::from(Document::LEGACY_CLOUDFLARE_PATH);
+                if path_buf.exists() {
+                    let cloudflare = read_cloudflare(&path_buf, &mut unknown_keys)?;
+                    paths.push(path_buf);
+                    cloudflare
+                }
+                else {
+                    // Allow running purely from environment variables, e.g. inside containers
+                    info!(
+                        target: "configuration.document",
+                        "Default cloudflare configuration file does not exist: Rely on environment variables."
+                    );
+                    Cloudflare::default()
+                }
+            }
+        };
+
+        Ok(Document { cloudflare, config, unknown_keys, paths })
+    }
+}
+
+fn default_config_path() -> Option<PathBuf> {
+    Document::DEFAULT_NAMES.iter()
+        .map(|name| Path::new(Document::DEFAULT_DIRECTORY).join(name))
+        .find(|path_buf| path_buf.is_file())
+}
+
+/**
+ * A configuration file is in the unified layout when it has a top-level `cloudflare` or `config` table,
+ * otherwise it is the legacy layout holding only the fields of [Config].
+ **/
+fn read_config(path_buf: &Path, unknown_keys: &mut Vec<UnknownKey>) -> Result<(Option<Cloudflare>, Config)> {
+    use super::error::Error;
+    let config_import_fail = || Error::ConfigImportFail(path_buf.to_string_lossy().to_string());
+    let config_parse_fail = |error| Error::ParseFail(path_buf.to_string_lossy().to_string(), error);
+
+    use std::fs::read_to_string;
+    let config_str = read_to_string(path_buf).map_err(|_| config_import_fail())?;
+
+    let format = Format::from_path(path_buf);
+    let is_unified = format.deserialize::<serde_json::Value>(&config_str)
+        .map_err(config_parse_fail)?
+        .as_object()
+        .is_some_and(|object| object.contains_key("cloudflare") || object.contains_key("config"));
+
+    let path = path_buf.to_string_lossy();
+    if is_unified {
+        let (unified, unknown): (Unified, _) = format.deserialize_unknown(&config_str).map_err(config_parse_fail)?;
+        unknown_keys.extend(unknown.iter().map(|segments| unknown_key::<Unified>(&path, segments)));
+        if unified.cloudflare.as_ref().is_some_and(|cloudflare| !cloudflare.token.is_empty()) {
+            use super::token::warn_exposed;
+            warn_exposed(path_buf);
+        }
+        Ok((unified.cloudflare, unified.config))
+    }
+    else {
+        let (config, unknown) = format.deserialize_unknown(&config_str).map_err(config_parse_fail)?;
+        unknown_keys.extend(unknown.iter().map(|segments| unknown_key::<Config>(&path, segments)));
+        Ok((None, config))
+    }
+}
+
+fn read_cloudflare(path_buf: &Path, unknown_keys: &mut Vec<UnknownKey>) -> Result<Cloudflare> {
+    use super::error::Error;
+    let cloudflare_import_fail = || Error::CloudflareImportFail(path_buf.to_string_lossy().to_string());
+    let cloudflare_parse_fail = |error| Error::ParseFail(path_buf.to_string_lossy().to_string(), error);
+
+    use std::fs::read_to_string;
+    let cloudflare_config_str = read_to_string(path_buf).map_err(|_| cloudflare_import_fail())?;
+
+    let (cloudflare, unknown): (Cloudflare, _) = Format::from_path(path_buf)
+        .deserialize_unknown(&cloudflare_config_str)
+        .map_err(cloudflare_parse_fail)?;
+    let path = path_buf.to_string_lossy();
+    unknown_keys.extend(unknown.iter().map(|segments| unknown_key::<Cloudflare>(&path, segments)));
+
+    if !cloudflare.token.is_empty() {
+        use super::token::warn_exposed;
+        warn_exposed(path_buf);
+    }
+    Ok(cloudflare)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Format, Unified};
+
+    #[test]
+    fn test_unified_layout() {
+        let json = r#"{
+            "cloudflare": { "token": "token", "zone": "zone", "domain-names": [{ "name": "example.com" }] },
+            "config": { "unavailable-hide": false }
+        }"#;
+        let toml = r#"
+            [cloudflare]
+            token = "token"
+            zone = "zone"
+
+            [[cloudflare.domain-names]]
+            name = "example.com"
+
+            [config]
+            unavailable-hide = false
+        "#;
+        let yaml = r#"
+            cloudflare:
+              token: token
+              zone: zone
+              domain-names:
+                - name: example.com
+            config:
+              unavailable-hide: false
+        "#;
+        for (format, str) in [(Format::Json, json), (Format::Toml, toml), (Format::Yaml, yaml)] {
+            let unified: Unified = format.deserialize(str).unwrap();
+            let cloudflare = unified.cloudflare.unwrap();
+            assert_eq!(cloudflare.zone, "zone");
+            assert_eq!(cloudflare.domain_names[0].name, "example.com");
+            assert!(!unified.config.unavailable_hide);
+        }
+    }
+
+}
@@ -0,0 +1,221 @@
+use std::{
+    env,
+    str::FromStr
+};
+
+use serde::de::DeserializeOwned;
+
+use super::{
+    cloudflare::Cloudflare,
+    config::Config,
+    unknown_key::UnknownKey,
+    Result
+};
+
+pub(super) mod variable {
+    pub const CLOUDFLARE_API_TOKEN: &str = "CLOUDFLARE_API_TOKEN";
+    pub const CLOUDFLARE: &str = "CFDDNS_CLOUDFLARE";
+    pub const CONFIG: &str = "CFDDNS_CONFIG";
+    pub const TOKEN: &str = "CFDDNS_TOKEN";
+    pub const TOKEN_FILE: &str = "CFDDNS_TOKEN_FILE";
+    pub const ZONE: &str = "CFDDNS_ZONE";
+    pub const ZONE_NAME: &str = "CFDDNS_ZONE_NAME";
+    pub const DOMAIN_NAMES: &str = "CFDDNS_DOMAIN_NAMES";
+    pub const PATTERNS: &str = "CFDDNS_PATTERNS";
+    pub const UNAVAILABLE_HIDE: &str = "CFDDNS_UNAVAILABLE_HIDE";
+    pub const UNAVAILABLE_CHECKS: &str = "CFDDNS_UNAVAILABLE_CHECKS";
+    pub const UNAVAILABLE_GRACE: &str = "CFDDNS_UNAVAILABLE_GRACE";
+    pub const UNAVAILABLE_ACTION: &str = "CFDDNS_UNAVAILABLE_ACTION";
+    pub const STATE_FILE: &str = "CFDDNS_STATE_FILE";
+    pub const STATUS_FILE: &str = "CFDDNS_STATUS_FILE";
+    pub const STATUS_FORMAT: &str = "CFDDNS_STATUS_FORMAT";
+    pub const STRICT: &str = "CFDDNS_STRICT";
+    pub const INTERVAL: &str = "CFDDNS_INTERVAL";
+    pub const CLEANUP_REMOVED: &str = "CFDDNS_CLEANUP_REMOVED";
+    pub const CLOUDFLARE_API_URL: &str = "CFDDNS_CLOUDFLARE_API_URL";
+    pub const IP_SB_URL_V4: &str = "CFDDNS_IP_SB_URL_V4";
+    pub const IP_SB_URL_V6: &str = "CFDDNS_IP_SB_URL_V6";
+    pub const IP_SB_TIMEOUT: &str = "CFDDNS_IP_SB_TIMEOUT";
+    pub const CLOUDFLARE_TIMEOUT: &str = "CFDDNS_CLOUDFLARE_TIMEOUT";
+    pub const CLOUDFLARE_PROXY: &str = "CFDDNS_CLOUDFLARE_PROXY";
+    pub const IP_SB_PROXY: &str = "CFDDNS_IP_SB_PROXY";
+    pub const PROVIDERS: &str = "CFDDNS_PROVIDERS";
+    pub const NOTIFICATIONS: &str = "CFDDNS_NOTIFICATIONS";
+    pub const NOTIFY_FAILURES_AFTER: &str = "CFDDNS_NOTIFY_FAILURES_AFTER";
+    pub const SMTP: &str = "CFDDNS_SMTP";
+    pub const SMTP_PASSWORD: &str = "CFDDNS_SMTP_PASSWORD";
+    pub const PRE_CHANGE: &str = "CFDDNS_PRE_CHANGE";
+    pub const ON_CHANGE: &str = "CFDDNS_ON_CHANGE";
+    pub const ON_ERROR: &str = "CFDDNS_ON_ERROR";
+    pub const HOOK_TIMEOUT: &str = "CFDDNS_HOOK_TIMEOUT";
+    pub const METRICS_LISTEN: &str = "CFDDNS_METRICS_LISTEN";
+    pub const HEALTHZ_FAILURES: &str = "CFDDNS_HEALTHZ_FAILURES";
+}
+
+/**
+ * Variables set to an empty string are treated as unset,
+ * so `CFDDNS_ZONE=` in a compose file does not wipe the configured zone.
+ **/
+pub(super) fn var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+pub(super) fn parse<T: FromStr>(name: &str) -> Result<Option<T>> {
+    var(name)
+        .map(|value| value.trim().parse::<T>())
+        .transpose()
+        .map_err(|_| {
+            use super::error::Error;
+            Error::EnvironmentInvalid(name.to_string())
+        })
+}
+
+pub(super) fn parse_bool(name: &str) -> Result<Option<bool>> {
+    var(name)
+        .map(|value| match value.trim().to_ascii_lowercase().as_str() {
+            "on" | "1" | "true" | "yes" | "enable" => Ok(true),
+            "off" | "0" | "false" | "no" | "disable" => Ok(false),
+            _ => {
+                use super::error::Error;
+                Err(Error::EnvironmentInvalid(name.to_string()))
+            }
+        })
+        .transpose()
+}
+
+/**
+ * Json values are checked for unknown keys like a configuration file, reported under the name of the variable
+ **/
+pub(super) fn parse_json<T: DeserializeOwned>(name: &str, unknown_keys: &mut Vec<UnknownKey>) -> Result<Option<T>> {
+    let Some(value) = var(name) else {
+        return Ok(None);
+    };
+    use super::format::Format;
+    let (value, unknown) = Format::Json.deserialize_unknown::<T>(&value)
+        .map_err(|_| {
+            use super::error::Error;
+            Error::EnvironmentInvalid(name.to_string())
+        })?;
+    use super::unknown_key::unknown_key;
+    unknown_keys.extend(unknown.iter().map(|segments| unknown_key::<T>(name, segments)));
+    Ok(Some(value))
+}
+
+pub(super) trait OverrideEnvironment {
+    fn override_environment(&mut self, unknown_keys: &mut Vec<UnknownKey>) -> Result<()>;
+}
+
+impl OverrideEnvironment for Cloudflare {
+    fn override_environment(&mut self, unknown_keys: &mut Vec<UnknownKey>) -> Result<()> {
+        // A token from the environment always outranks whatever the file declares
+        if let Some(token) = var(variable::TOKEN) {
+            self.token = token;
+            self.token_file = None;
+        }
+        if let Some(token_file) = var(variable::TOKEN_FILE) {
+            use std::path::PathBuf;
+            self.token_file = Some(PathBuf::from(token_file));
+        }
+        if let Some(zone) = var(variable::ZONE) {
+            self.zone = zone;
+        }
+        if let Some(zone_name) = var(variable::ZONE_NAME) {
+            self.zone_name = Some(zone_name);
+        }
+        if let Some(domain_names) = parse_json(variable::DOMAIN_NAMES, unknown_keys)? {
+            self.domain_names = domain_names;
+        }
+        if let Some(patterns) = parse_json(variable::PATTERNS, unknown_keys)? {
+            self.patterns = patterns;
+        }
+        Ok(())
+    }
+}
+
+impl OverrideEnvironment for Config {
+    fn override_environment(&mut self, unknown_keys: &mut Vec<UnknownKey>) -> Result<()> {
+        if let Some(unavailable_hide) = parse_bool(variable::UNAVAILABLE_HIDE)? {
+            self.unavailable_hide = unavailable_hide;
+        }
+        if let Some(unavailable_checks) = parse(variable::UNAVAILABLE_CHECKS)? {
+            self.unavailable_checks = unavailable_checks;
+        }
+        if let Some(unavailable_grace) = parse(variable::UNAVAILABLE_GRACE)? {
+            self.unavailable_grace = unavailable_grace;
+        }
+        if let Some(unavailable_action) = parse(variable::UNAVAILABLE_ACTION)? {
+            self.unavailable_action = unavailable_action;
+        }
+        if let Some(state_file) = var(variable::STATE_FILE) {
+            use std::path::PathBuf;
+            self.state_file = Some(PathBuf::from(state_file));
+        }
+        if let Some(status_file) = var(variable::STATUS_FILE) {
+            use std::path::PathBuf;
+            self.status_file = Some(PathBuf::from(status_file));
+        }
+        if let Some(status_format) = parse(variable::STATUS_FORMAT)? {
+            self.status_format = status_format;
+        }
+        if let Some(strict) = parse_bool(variable::STRICT)? {
+            self.strict = strict;
+        }
+        if let Some(interval) = parse(variable::INTERVAL)? {
+            self.interval = interval;
+        }
+        if let Some(cleanup_removed) = parse_bool(variable::CLEANUP_REMOVED)? {
+            self.cleanup_removed = cleanup_removed;
+        }
+        if let Some(cloudflare_api_url) = var(variable::CLOUDFLARE_API_URL) {
+            self.cloudflare_api_url = Some(cloudflare_api_url);
+        }
+        if let Some(ip_sb_url_v4) = var(variable::IP_SB_URL_V4) {
+            self.ip_sb_url_v4 = Some(ip_sb_url_v4);
+        }
+        if let Some(ip_sb_url_v6) = var(variable::IP_SB_URL_V6) {
+            self.ip_sb_url_v6 = Some(ip_sb_url_v6);
+        }
+        if let Some(cloudflare_proxy) = var(variable::CLOUDFLARE_PROXY) {
+            self.cloudflare_proxy = cloudflare_proxy.into();
+        }
+        if let Some(ip_sb_proxy) = var(variable::IP_SB_PROXY) {
+            self.ip_sb_proxy = ip_sb_proxy.into();
+        }
+        if let Some(providers) = parse_json(variable::PROVIDERS, unknown_keys)? {
+            self.providers = providers;
+        }
+        if let Some(notifications) = parse_json(variable::NOTIFICATIONS, unknown_keys)? {
+            self.notifications = notifications;
+        }
+        if let Some(notify_failures_after) = parse(variable::NOTIFY_FAILURES_AFTER)? {
+            self.notify_failures_after = notify_failures_after;
+        }
+        if let Some(smtp) = parse_json(variable::SMTP, unknown_keys)? {
+            self.smtp = smtp;
+        }
+        // Keeps the password out of a configuration file or `CFDDNS_SMTP`
+        if let Some(smtp) = &mut self.smtp && let Some(password) = var(variable::SMTP_PASSWORD) {
+            smtp.password = Some(password);
+        }
+        if let Some(pre_change) = var(variable::PRE_CHANGE) {
+            self.pre_change = Some(pre_change);
+        }
+        if let Some(on_change) = var(variable::ON_CHANGE) {
+            self.on_change = Some(on_change);
+        }
+        if let Some(on_error) = var(variable::ON_ERROR) {
+            self.on_error = Some(on_error);
+        }
+        if let Some(hook_timeout) = parse(variable::HOOK_TIMEOUT)? {
+            self.hook_timeout = hook_timeout;
+        }
+        if let Some(metrics_listen) = parse(variable::METRICS_LISTEN)? {
+            self.metrics_listen = Some(metrics_listen);
+        }
+        if let Some(healthz_failures) = parse(variable::HEALTHZ_FAILURES)? {
+            self.healthz_failures = healthz_failures;
+        }
+        self.ip_sb_timeout.override_environment(variable::IP_SB_TIMEOUT)?;
+        self.cloudflare_timeout.override_environment(variable::CLOUDFLARE_TIMEOUT)
+    }
+}
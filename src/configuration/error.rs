@@ -1,4 +1,41 @@
+use std::fmt::{Display, Formatter};
+
+use super::{
+    format::ParseError,
+    unknown_key::UnknownKey,
+    validate::Issue
+};
+
 pub enum Error {
     CloudflareImportFail(String),
-    ConfigImportFail(String)
-}
\ No newline at end of file
+    ConfigImportFail(String),
+    ParseFail(String, ParseError),
+    TokenImportFail(String),
+    TokenMissing,
+    ZoneMissing,
+    EnvironmentInvalid(String),
+    Invalid(Vec<Issue>),
+    UnknownKeys(Vec<UnknownKey>)
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::CloudflareImportFail(path) => { write!(f, "Failed to import Cloudflare config from {path}") }
+            Error::ConfigImportFail(path) => { write!(f, "Failed to import config from {path}") }
+            Error::ParseFail(path, error) => { write!(f, "Failed to parse {path}: {error}") }
+            Error::TokenImportFail(path) => { write!(f, "Failed to read Cloudflare api token from {path}") }
+            Error::TokenMissing => { f.write_str("Cloudflare api token is missing or empty") }
+            Error::ZoneMissing => { f.write_str("Cloudflare zone is missing or empty") }
+            Error::EnvironmentInvalid(name) => { write!(f, "Invalid value of environment variable {name}") }
+            Error::Invalid(issues) => {
+                f.write_str("Invalid configuration:")?;
+                issues.iter().try_for_each(|issue| write!(f, "\n  {issue}"))
+            }
+            Error::UnknownKeys(unknown_keys) => {
+                f.write_str("Unknown configuration keys, set `strict` to false or pass --lenient to ignore them:")?;
+                unknown_keys.iter().try_for_each(|unknown_key| write!(f, "\n  {unknown_key}"))
+            }
+        }
+    }
+}
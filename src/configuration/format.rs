@@ -0,0 +1,191 @@
+use std::{
+    fmt::{Display, Formatter},
+    path::Path
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::unknown_key::{segments, Segment};
+
+/**
+ * Configuration file formats, detected from the file extension.
+ * Anything other than `.toml`, `.yaml` and `.yml` is read as json, as it always was.
+ **/
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum Format {
+    #[serde(rename = "json")]
+    Json,
+    #[serde(rename = "toml")]
+    Toml,
+    #[serde(rename = "yaml", alias = "yml")]
+    Yaml
+}
+
+/**
+ * Where and why a configuration file failed to deserialize.
+ * `line` and `column` are 1-based, `key` is the path to the offending value, e.g. `cloudflare.domain-names[0].name`.
+ **/
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: Option<usize>,
+    pub column: Option<usize>,
+    pub key: Option<String>,
+    pub message: String
+}
+
+impl Format {
+
+    pub(super) fn from_path(path: &Path) -> Self {
+        let extension = path.extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        match extension.as_deref() {
+            Some("toml") => Self::Toml,
+            Some("yaml" | "yml") => Self::Yaml,
+            _ => Self::Json
+        }
+    }
+
+    pub(super) fn from_name(name: &str) -> Option<Self> {
+        serde_json::from_slice(format!(r#""{}""#, name.to_ascii_lowercase()).as_bytes()).ok()
+    }
+
+    pub(super) fn deserialize<T: DeserializeOwned>(&self, str: &str) -> Result<T, ParseError> {
+        self.deserialize_unknown(str).map(|(value, _)| value)
+    }
+
+    /**
+     * Deserialize and collect the path of every key that no field consumed
+     **/
+    pub(super) fn deserialize_unknown<T: DeserializeOwned>(&self, str: &str) -> Result<(T, Vec<Vec<Segment>>), ParseError> {
+        let mut unknown = Vec::new();
+        let mut callback = |path: serde_ignored::Path| unknown.push(segments(&path));
+        let value = match self {
+            Self::Json => {
+                let mut deserializer = serde_json::Deserializer::from_str(str);
+                let value = serde_path_to_error::deserialize(serde_ignored::Deserializer::new(&mut deserializer, &mut callback))
+                    .map_err(|error| {
+                        let key = key(error.path());
+                        let error = error.into_inner();
+                        ParseError::new(Some(error.line()), Some(error.column()), key, error.to_string())
+                    })?;
+                deserializer.end()
+                    .map_err(|error| ParseError::new(Some(error.line()), Some(error.column()), None, error.to_string()))?;
+                value
+            }
+            Self::Toml => {
+                let toml_error = |key: Option<String>, error: toml::de::Error| {
+                    let (line, column) = error.span()
+                        .map(|span| line_column(str, span.start))
+                        .unzip();
+                    ParseError::new(line, column, key, error.message().to_string())
+                };
+                let deserializer = toml::Deserializer::parse(str)
+                    .map_err(|error| toml_error(None, error))?;
+                serde_path_to_error::deserialize(serde_ignored::Deserializer::new(deserializer, &mut callback))
+                    .map_err(|error| {
+                        let key = key(error.path());
+                        toml_error(key, error.into_inner())
+                    })?
+            }
+            Self::Yaml => {
+                let deserializer = serde_yaml::Deserializer::from_str(str);
+                serde_path_to_error::deserialize(serde_ignored::Deserializer::new(deserializer, &mut callback))
+                    .map_err(|error| {
+                        let key = key(error.path());
+                        let error = error.into_inner();
+                        let (line, column) = error.location()
+                            .map(|location| (location.line(), location.column()))
+                            .unzip();
+                        ParseError::new(line, column, key, error.to_string())
+                    })?
+            }
+        };
+        Ok((value, unknown))
+    }
+
+    pub(super) fn serialize<T: Serialize>(&self, value: &T) -> Option<String> {
+        match self {
+            Self::Json => serde_json::to_string_pretty(value).ok(),
+            Self::Toml => toml::to_string_pretty(value).ok(),
+            Self::Yaml => serde_yaml::to_string(value).ok()
+        }
+    }
+
+}
+
+impl ParseError {
+
+    fn new(line: Option<usize>, column: Option<usize>, key: Option<String>, message: String) -> Self {
+        // serde_json and serde_yaml append the position to their messages, it is reported separately
+        let message = match (line, column) {
+            (Some(line), Some(column)) => message
+                .trim_end_matches(&format!(" at line {line} column {column}"))
+                .to_string(),
+            _ => message
+        };
+        Self { line, column, key, message }
+    }
+
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let (Some(line), Some(column)) = (self.line, self.column) {
+            write!(f, "line {line}, column {column}: ")?;
+        }
+        if let Some(key) = &self.key {
+            write!(f, "`{key}`: ")?;
+        }
+        f.write_str(&self.message)
+    }
+}
+
+fn key(path: &serde_path_to_error::Path) -> Option<String> {
+    let key = path.to_string();
+    if key == "." { None } else { Some(key) }
+}
+
+fn line_column(str: &str, offset: usize) -> (usize, usize) {
+    let before = &str[..offset.min(str.len())];
+    let line = before.matches('\n').count() + 1;
+    let column = before.rfind('\n').map_or(before.len(), |index| before.len() - index - 1) + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::{
+        super::cloudflare::Cloudflare,
+        Format
+    };
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(Format::from_path(Path::new("cloudflare.conf.json")), Format::Json);
+        assert_eq!(Format::from_path(Path::new("config.TOML")), Format::Toml);
+        assert_eq!(Format::from_path(Path::new("config.yml")), Format::Yaml);
+        assert_eq!(Format::from_path(Path::new("config.yaml")), Format::Yaml);
+        assert_eq!(Format::from_path(Path::new("config")), Format::Json);
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let json = "{\n  \"zone\": \"zone\",\n  \"domain-names\": [{ \"name\": 1 }]\n}";
+        let error = Format::Json.deserialize::<Cloudflare>(json).err().unwrap();
+        assert_eq!((error.line, error.column), (Some(3), Some(30)));
+        assert_eq!(error.key.as_deref(), Some("domain-names[0].name"));
+
+        let toml = "zone = \"zone\"\n\n[[domain-names]]\nname = 1\n";
+        let error = Format::Toml.deserialize::<Cloudflare>(toml).err().unwrap();
+        assert_eq!((error.line, error.column), (Some(4), Some(8)));
+        assert_eq!(error.key.as_deref(), Some("domain-names[0].name"));
+
+        let yaml = "zone: zone\ndomain-names:\n  - name: [1]\n";
+        let error = Format::Yaml.deserialize::<Cloudflare>(yaml).err().unwrap();
+        assert_eq!(error.line, Some(3));
+        assert_eq!(error.key.as_deref(), Some("domain-names[0].name"));
+    }
+
+}
@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+use super::{
+    argument::Argument,
+    cloudflare::Cloudflare,
+    config::Config,
+    format::Format,
+    Configuration
+};
+
+pub(super) trait GetPrintConfig {
+    fn get_print_config(&self) -> Option<Format>;
+}
+
+impl GetPrintConfig for Vec<Argument> {
+    fn get_print_config(&self) -> Option<Format> {
+        self.iter()
+            .find_map(|argument|
+                if let Argument::PrintConfig(format) = argument { Some(*format) }
+                else { None }
+            )
+    }
+}
+
+/**
+ * Effective configuration in the unified layout, `token` is redacted on serializing
+ **/
+#[derive(Serialize)]
+struct Effective<'configuration> {
+    cloudflare: &'configuration Cloudflare,
+    config: &'configuration Config
+}
+
+impl Configuration {
+    pub fn to_redacted_string(&self, format: Format) -> Option<String> {
+        let effective = Effective { cloudflare: &self.cloudflare, config: &self.config };
+        format.serialize(&effective)
+    }
+}
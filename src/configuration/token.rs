@@ -0,0 +1,84 @@
+use std::path::Path;
+
+use super::{
+    argument::Argument,
+    cloudflare::Cloudflare,
+    Result
+};
+
+pub(super) trait GetToken {
+    fn get_token(&self, cloudflare: &Cloudflare) -> Result<String>;
+}
+
+/**
+ * Token sources, from the highest priority:
+ * `--token-stdin`, `CLOUDFLARE_API_TOKEN`, `token-file` and finally `token`.
+ * `CFDDNS_TOKEN` and `CFDDNS_TOKEN_FILE` have already replaced their fields at this point.
+ **/
+impl GetToken for Vec<Argument> {
+    fn get_token(&self, cloudflare: &Cloudflare) -> Result<String> {
+        use super::error::Error;
+
+        if self.iter().any(|argument| matches!(argument, Argument::TokenStdin)) {
+            use std::io::{read_to_string, stdin};
+            let token = read_to_string(stdin())
+                .map_err(|_| Error::TokenImportFail(String::from("<stdin>")))?;
+            return non_empty(token);
+        }
+
+        use super::environment::{var, variable};
+        if let Some(token) = var(variable::CLOUDFLARE_API_TOKEN) {
+            return non_empty(token);
+        }
+
+        if let Some(token_file) = &cloudflare.token_file {
+            use std::fs::read_to_string;
+            let token = read_to_string(token_file)
+                .map_err(|_| Error::TokenImportFail(token_file.to_string_lossy().to_string()))?;
+            warn_exposed(token_file);
+            return non_empty(token);
+        }
+
+        non_empty(cloudflare.token.clone())
+    }
+}
+
+fn non_empty(token: String) -> Result<String> {
+    let token = token.trim();
+    if token.is_empty() {
+        use super::error::Error;
+        return Err(Error::TokenMissing);
+    }
+    Ok(token.to_string())
+}
+
+/**
+ * Warn when a file holding the token can be read by anyone but its owner
+ **/
+pub(super) fn warn_exposed(path: &Path) {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let Ok(metadata) = path.metadata() else { return; };
+        let mode = metadata.permissions().mode();
+        if mode & 0o044 != 0 {
+            use log::warn;
+            warn!(
+                target: "configuration.token",
+                "{} holds the Cloudflare api token but is readable by group or others (mode {:o}), consider `chmod 600`",
+                path.display(), mode & 0o777
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::non_empty;
+
+    #[test]
+    fn test_non_empty() {
+        assert_eq!(non_empty(String::from("token\n")).ok().unwrap(), "token");
+        assert!(non_empty(String::from(" \n")).is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt::{Display, Formatter};
+use serde::de::DeserializeOwned;
+
+mod probe;
+
+pub enum Segment {
+    Key(String),
+    Index(usize)
+}
+
+/**
+ * A key present in a configuration file or a json environment variable that no field consumed
+ **/
+pub struct UnknownKey {
+    pub path: String,
+    pub key: String,
+    pub suggestion: Option<&'static str>
+}
+
+impl Display for UnknownKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: unknown key `{}`", self.path, self.key)?;
+        if let Some(suggestion) = self.suggestion {
+            write!(f, ", did you mean `{suggestion}`?")?;
+        }
+        Ok(())
+    }
+}
+
+pub(super) fn segments(path: &serde_ignored::Path) -> Vec<Segment> {
+    use serde_ignored::Path;
+    let mut segments = Vec::new();
+    let mut current = path;
+    loop {
+        current = match current {
+            Path::Root => break,
+            Path::Seq { parent, index } => {
+                segments.push(Segment::Index(*index));
+                parent
+            }
+            Path::Map { parent, key } => {
+                segments.push(Segment::Key(key.to_owned()));
+                parent
+            }
+            Path::Some { parent } | Path::NewtypeStruct { parent } | Path::NewtypeVariant { parent } => parent
+        };
+    }
+    segments.reverse();
+    segments
+}
+
+/**
+ * Name the key at `segments` of a document deserialized as `T`, suggesting the closest key `T` accepts at the same level
+ **/
+pub(super) fn unknown_key<T: DeserializeOwned>(path: &str, segments: &[Segment]) -> UnknownKey {
+    let key = segments.iter().enumerate()
+        .map(|(index, segment)| match segment {
+            Segment::Key(key) if index == 0 => key.to_owned(),
+            Segment::Key(key) => format!(".{key}"),
+            Segment::Index(index) => format!("[{index}]")
+        })
+        .collect::<String>();
+
+    let suggestion = match segments.split_last() {
+        Some((Segment::Key(unknown), parents)) => known_keys::<T>(parents).and_then(|known_keys| suggest(unknown, known_keys)),
+        _ => None
+    };
+    UnknownKey { path: path.to_string(), key, suggestion }
+}
+
+/**
+ * Keys of the struct at `segments`, every name and alias exactly as serde derived them
+ **/
+fn known_keys<T: DeserializeOwned>(segments: &[Segment]) -> Option<&'static [&'static str]> {
+    use std::cell::Cell;
+    use probe::Probe;
+    let found = Cell::new(None);
+    let _ = T::deserialize(Probe { segments, found: &found });
+    found.get()
+}
+
+fn suggest(unknown: &str, known_keys: &[&'static str]) -> Option<&'static str> {
+    let unknown = normalize(unknown);
+    // Canonical names come before their aliases, the first of equally close keys is kept
+    known_keys.iter()
+        .map(|name| (distance(&unknown, &normalize(name)), *name))
+        .filter(|(distance, _)| *distance <= (unknown.chars().count() / 3).max(2))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, name)| name)
+}
+
+fn normalize(key: &str) -> String {
+    key.to_ascii_lowercase().replace(['_', '.'], "-")
+}
+
+/**
+ * Levenshtein distance
+ **/
+fn distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        super::{
+            cloudflare::domain_name::DomainName,
+            config::smtp::Smtp,
+            document::Unified,
+            format::Format
+        },
+        distance,
+        unknown_key
+    };
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("domain-type", "domain-type"), 0);
+        assert_eq!(distance("domian-type", "domain-type"), 2);
+        assert_eq!(distance("kitten", "sitting"), 3);
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let (_, unknown_keys) = Format::Json.deserialize_unknown::<Unified>(r#"{
+            "cloudflare": {
+                "zone": "zone",
+                "domain-names": [{ "name": "example.com", "domian-type": "AAAA", "time_to_live": 300 }],
+                "comment": "home"
+            },
+            "config": { "unavailable_hide": false, "cloudflare-timeout": { "totl": 10 } }
+        }"#).unwrap();
+        let unknown_keys = unknown_keys.iter()
+            .map(|segments| unknown_key::<Unified>("config.json", segments).to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            unknown_keys,
+            [
+                "config.json: unknown key `cloudflare.domain-names[0].domian-type`, did you mean `domain-type`?",
+                "config.json: unknown key `cloudflare.comment`",
+                "config.json: unknown key `config.cloudflare-timeout.totl`, did you mean `total`?"
+            ]
+        );
+    }
+
+    /**
+     * Json of environment variables is rooted at the overridden field rather than a whole document
+     **/
+    #[test]
+    fn test_unknown_keys_of_environment() {
+        let (_, unknown_keys) = Format::Json.deserialize_unknown::<Vec<DomainName>>(
+            r#"[{ "name": "example.com" }, { "name": "www.example.com", "proxid": false }]"#
+        ).unwrap();
+        assert_eq!(
+            unknown_key::<Vec<DomainName>>("CFDDNS_DOMAIN_NAMES", &unknown_keys[0]).to_string(),
+            "CFDDNS_DOMAIN_NAMES: unknown key `[1].proxid`, did you mean `proxied`?"
+        );
+
+        let (_, unknown_keys) = Format::Json.deserialize_unknown::<Option<Smtp>>(
+            r#"{ "host": "smtp.example.com", "from": "a@example.com", "to": ["b@example.com"], "user-name": "a" }"#
+        ).unwrap();
+        assert_eq!(
+            unknown_key::<Option<Smtp>>("CFDDNS_SMTP", &unknown_keys[0]).to_string(),
+            "CFDDNS_SMTP: unknown key `user-name`, did you mean `username`?"
+        );
+    }
+
+}
@@ -0,0 +1,118 @@
+use std::{
+    cell::Cell,
+    fmt::{Display, Formatter}
+};
+use serde::{
+    de::{DeserializeSeed, MapAccess, SeqAccess, Visitor},
+    forward_to_deserialize_any,
+    Deserializer
+};
+
+use super::Segment;
+
+/**
+ * A deserializer without data walking down `segments`, it records the fields of the struct found at the end
+ * and gives up everywhere else. Every field and alias comes from the derive, nothing is listed by hand.
+ **/
+pub(super) struct Probe<'a> {
+    pub segments: &'a [Segment],
+    pub found: &'a Cell<Option<&'static [&'static str]>>
+}
+
+#[derive(Debug)]
+pub(super) struct Stop;
+
+impl Display for Stop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str("probe stopped")
+    }
+}
+
+impl std::error::Error for Stop {}
+
+impl serde::de::Error for Stop {
+    fn custom<T: Display>(_: T) -> Self { Stop }
+}
+
+impl<'de> Deserializer<'de> for Probe<'_> {
+    type Error = Stop;
+
+    /**
+     * Maps and untagged or flattened types only see the path, not the fields
+     **/
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Stop> {
+        match self.segments.split_first() {
+            Some((Segment::Key(key), segments)) => visitor.visit_map(Entry { key: Some(key), segments, found: self.found }),
+            Some((Segment::Index(_), segments)) => visitor.visit_seq(Element { is_done: false, segments, found: self.found }),
+            None => Err(Stop)
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self, _: &'static str, fields: &'static [&'static str], visitor: V
+    ) -> Result<V::Value, Stop> {
+        if self.segments.is_empty() {
+            self.found.set(Some(fields));
+            return Err(Stop);
+        }
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Stop> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _: &'static str, visitor: V) -> Result<V::Value, Stop> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf unit unit_struct
+        seq tuple tuple_struct map enum identifier ignored_any
+    }
+}
+
+/**
+ * The single key of the path at this level
+ **/
+struct Entry<'a> {
+    key: Option<&'a String>,
+    segments: &'a [Segment],
+    found: &'a Cell<Option<&'static [&'static str]>>
+}
+
+impl<'de> MapAccess<'de> for Entry<'_> {
+    type Error = Stop;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, Stop> {
+        use serde::de::IntoDeserializer;
+        self.key.take()
+            .map(|key| seed.deserialize(key.as_str().into_deserializer()))
+            .transpose()
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, Stop> {
+        seed.deserialize(Probe { segments: self.segments, found: self.found })
+    }
+}
+
+/**
+ * The single element of the path at this level, whichever index it had
+ **/
+struct Element<'a> {
+    is_done: bool,
+    segments: &'a [Segment],
+    found: &'a Cell<Option<&'static [&'static str]>>
+}
+
+impl<'de> SeqAccess<'de> for Element<'_> {
+    type Error = Stop;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, Stop> {
+        if self.is_done {
+            return Ok(None);
+        }
+        self.is_done = true;
+        seed.deserialize(Probe { segments: self.segments, found: self.found }).map(Some)
+    }
+}
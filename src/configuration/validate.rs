@@ -0,0 +1,485 @@
+use std::fmt::{Display, Formatter};
+
+use crate::rest_api::Proxy;
+use super::{
+    cloudflare::{domain_name::{source::Source, DomainName, DomainType}, Cloudflare},
+    config::{unavailable::UnavailableAction, Config}
+};
+
+/**
+ * A semantic problem found in an otherwise well-formed configuration,
+ * `key` follows the same path notation as [super::format::ParseError].
+ **/
+pub struct Issue {
+    pub key: String,
+    pub message: String
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`: {}", self.key, self.message)
+    }
+}
+
+pub(super) trait Validate {
+    fn validate(&self, key: &str, issues: &mut Vec<Issue>);
+}
+
+fn issue(issues: &mut Vec<Issue>, key: String, message: String) {
+    issues.push(Issue { key, message });
+}
+
+/**
+ * Characters of a record comment Cloudflare takes on free plans and on paid plans,
+ * see https://developers.cloudflare.com/dns/manage-dns-records/reference/record-attributes/#comments
+ **/
+const FREE_COMMENT_LIMIT: usize = 100;
+const COMMENT_LIMIT: usize = 500;
+
+impl Validate for Cloudflare {
+    fn validate(&self, key: &str, issues: &mut Vec<Issue>) {
+        let zone_name = self.zone_name.as_deref().map(normalize);
+        if let Some(zone_name) = &zone_name && !is_fqdn(zone_name) {
+            issue(issues, format!("{key}.zone-name"), format!("`{zone_name}` is not a valid domain name"));
+        }
+
+        for (index, domain_name) in self.domain_names.iter().enumerate() {
+            let key = format!("{key}.domain-names[{index}]");
+            domain_name.validate(&key, issues);
+
+            let name = normalize(&domain_name.name);
+            if let Some(zone_name) = &zone_name && name != *zone_name && !name.ends_with(&format!(".{zone_name}")) {
+                issue(issues, format!("{key}.name"), format!("`{name}` is not within zone `{zone_name}`"));
+            }
+
+            let duplicate = self.domain_names[..index].iter()
+                .position(|previous| normalize(&previous.name) == name && previous.domain_type == domain_name.domain_type);
+            if let Some(previous) = duplicate {
+                issue(
+                    issues,
+                    format!("{key}.name"),
+                    format!("`{name}` ({}) duplicates domain-names[{previous}]", domain_name.domain_type)
+                );
+            }
+        }
+
+        for (index, pattern) in self.patterns.iter().enumerate() {
+            let key = format!("{key}.patterns[{index}]");
+            if pattern.name.is_none() && pattern.tag.is_none() {
+                issue(issues, key.clone(), String::from("needs a `name` or a `tag` to match"));
+            }
+            if let Some(name) = &pattern.name {
+                let name = normalize(name);
+                if !name.contains('*') && !is_fqdn(&name) || !name.chars().all(|char| char.is_ascii_alphanumeric() || "-.*".contains(char)) {
+                    issue(issues, format!("{key}.name"), format!("`{name}` is neither a domain name nor a pattern of one"));
+                }
+                else if let Some(zone_name) = &zone_name && name != *zone_name && !name.ends_with(&format!(".{zone_name}")) {
+                    issue(issues, format!("{key}.name"), format!("`{name}` is not within zone `{zone_name}`"));
+                }
+            }
+            if let Some(tag) = &pattern.tag && tag.is_empty() {
+                issue(issues, format!("{key}.tag"), String::from("must not be empty"));
+            }
+        }
+    }
+}
+
+impl Validate for DomainName {
+    fn validate(&self, key: &str, issues: &mut Vec<Issue>) {
+        if !is_fqdn(&normalize(&self.name)) {
+            issue(issues, format!("{key}.name"), format!("`{}` is not a valid fully qualified domain name", self.name));
+        }
+        if let Some(ipv6_suffix) = &self.ipv6_suffix {
+            use crate::prefix::{is_host_id, PREFIX_LENGTH};
+            if self.domain_type != DomainType::AAAA {
+                issue(issues, format!("{key}.ipv6-suffix"), String::from("only applies to AAAA records"));
+            }
+            else if !is_host_id(ipv6_suffix) {
+                issue(issues, format!("{key}.ipv6-suffix"), format!("`{ipv6_suffix}` must leave the first {PREFIX_LENGTH} bits zero"));
+            }
+        }
+        if let Some(tags) = &self.tags && tags.iter().any(String::is_empty) {
+            issue(issues, format!("{key}.tags"), String::from("must not contain an empty tag"));
+        }
+        if let Some(template) = &self.comment {
+            let length = longest_comment(template, self).chars().count();
+            if length > COMMENT_LIMIT {
+                issue(
+                    issues,
+                    format!("{key}.comment"),
+                    format!("renders up to {length} characters with the ownership marker, Cloudflare takes at most {COMMENT_LIMIT}")
+                );
+            }
+            else if length > FREE_COMMENT_LIMIT {
+                use log::warn;
+                warn!(
+                    target: "configuration",
+                    "`{key}.comment` renders up to {length} characters with the ownership marker, free plans take at most {FREE_COMMENT_LIMIT}"
+                );
+            }
+        }
+        // See https://developers.cloudflare.com/api/resources/dns/subresources/records/models/ttl/#(schema)
+        if self.time_to_live != 1 && !(60..=86400).contains(&self.time_to_live) {
+            issue(
+                issues,
+                format!("{key}.time-to-live"),
+                format!("{} must be 1 (automatic) or between 60 and 86400", self.time_to_live)
+            );
+        }
+        else if self.proxied && self.time_to_live != 1 {
+            use log::warn;
+            warn!(
+                target: "configuration",
+                "`{key}.time-to-live` {} is left alone while the record is proxied, Cloudflare keeps proxied records at 1 (automatic)",
+                self.time_to_live
+            );
+        }
+    }
+}
+
+impl Validate for Config {
+    fn validate(&self, key: &str, issues: &mut Vec<Issue>) {
+        if self.interval == 0 {
+            issue(issues, format!("{key}.interval"), String::from("must be greater than 0"));
+        }
+        if self.unavailable_checks == 0 {
+            issue(issues, format!("{key}.unavailable-checks"), String::from("must be greater than 0"));
+        }
+        let urls = [
+            ("cloudflare-api-url", &self.cloudflare_api_url), ("ip-sb-url-v4", &self.ip_sb_url_v4), ("ip-sb-url-v6", &self.ip_sb_url_v6)
+        ];
+        for (name, url) in urls {
+            if let Some(url) = url && !is_http_url(url) {
+                issue(issues, format!("{key}.{name}"), format!("`{url}` is not a http or https url"));
+            }
+        }
+        for (index, provider) in self.providers.iter().enumerate() {
+            let key = format!("{key}.providers[{index}]");
+            if provider.name.is_empty() {
+                issue(issues, format!("{key}.name"), String::from("must not be empty"));
+            }
+            else if let Some(previous) = self.providers[..index].iter().position(|previous| previous.name == provider.name) {
+                issue(issues, format!("{key}.name"), format!("`{}` duplicates providers[{previous}]", provider.name));
+            }
+            for (name, url) in [("url-v4", &provider.url_v4), ("url-v6", &provider.url_v6)] {
+                if let Some(url) = url && !is_http_url(url) {
+                    issue(issues, format!("{key}.{name}"), format!("`{url}` is not a http or https url"));
+                }
+            }
+        }
+        for (index, notification) in self.notifications.iter().enumerate() {
+            let key = format!("{key}.notifications[{index}]");
+            if !is_http_url(&notification.url) {
+                issue(issues, format!("{key}.url"), String::from("is not a http or https url"));
+            }
+            use super::config::notification::Preset;
+            if notification.preset == Preset::Telegram && notification.chat_id.is_none() {
+                issue(issues, format!("{key}.chat-id"), String::from("is required by the telegram preset"));
+            }
+        }
+        if let Some(smtp) = &self.smtp {
+            let key = format!("{key}.smtp");
+            if smtp.host.is_empty() {
+                issue(issues, format!("{key}.host"), String::from("must not be empty"));
+            }
+            if smtp.username.is_some() != smtp.password.is_some() {
+                issue(issues, format!("{key}.password"), String::from("must be set along with username"));
+            }
+            use lettre::message::Mailbox;
+            if smtp.from.parse::<Mailbox>().is_err() {
+                issue(issues, format!("{key}.from"), format!("`{}` is not a mail address", smtp.from));
+            }
+            if smtp.to.is_empty() {
+                issue(issues, format!("{key}.to"), String::from("must not be empty"));
+            }
+            if smtp.timeout == 0 {
+                issue(issues, format!("{key}.timeout"), String::from("must be greater than 0"));
+            }
+            for (index, to) in smtp.to.iter().enumerate() {
+                if to.parse::<Mailbox>().is_err() {
+                    issue(issues, format!("{key}.to[{index}]"), format!("`{to}` is not a mail address"));
+                }
+            }
+        }
+        if self.notify_failures_after == 0 {
+            issue(issues, format!("{key}.notify-failures-after"), String::from("must be greater than 0"));
+        }
+        if self.hook_timeout == 0 {
+            issue(issues, format!("{key}.hook-timeout"), String::from("must be greater than 0"));
+        }
+        if self.healthz_failures == 0 {
+            issue(issues, format!("{key}.healthz-failures"), String::from("must be greater than 0"));
+        }
+        for (name, proxy) in [("cloudflare-proxy", &self.cloudflare_proxy), ("ip-sb-proxy", &self.ip_sb_proxy)] {
+            if let Proxy::Url(url) = proxy && !is_proxy_url(url) {
+                issue(
+                    issues,
+                    format!("{key}.{name}"),
+                    format!("`{}` is neither `environment`, `direct` nor a {} proxy url", proxy.to_redacted_string(), Proxy::SCHEMES.join(", "))
+                );
+            }
+        }
+        for (name, timeout) in [("ip-sb-timeout", &self.ip_sb_timeout), ("cloudflare-timeout", &self.cloudflare_timeout)] {
+            let (total, connect, read) = timeout.all();
+            for (field, value) in [("total", total), ("connect", connect), ("read", read)] {
+                if value == 0 {
+                    issue(issues, format!("{key}.{name}.{field}"), String::from("must be greater than 0"));
+                }
+            }
+            if connect > total {
+                issue(
+                    issues,
+                    format!("{key}.{name}.connect"),
+                    format!("{connect} must not be greater than total timeout {total}")
+                );
+            }
+        }
+    }
+}
+
+/**
+ * Checks across sections, every provider a domain name refers to must be declared in `config.providers`
+ * and parking needs a placeholder of every record type configured
+ **/
+pub(super) fn validate_references(cloudflare: &Cloudflare, config: &Config, issues: &mut Vec<Issue>) {
+    if config.unavailable_hide && config.unavailable_action == UnavailableAction::Park {
+        for (domain_type, name, is_missing) in [(DomainType::A, "v4", config.park.v4.is_none()), (DomainType::AAAA, "v6", config.park.v6.is_none())] {
+            if is_missing && cloudflare.domain_names.iter().any(|domain_name| domain_name.domain_type == domain_type) {
+                issue(issues, format!("config.park.{name}"), format!("is required to park {domain_type} records"));
+            }
+        }
+    }
+    for (index, domain_name) in cloudflare.domain_names.iter().enumerate() {
+        validate_source(&format!("cloudflare.domain-names[{index}]"), domain_name, config, issues);
+    }
+    for (index, pattern) in cloudflare.patterns.iter().enumerate() {
+        validate_source(&format!("cloudflare.patterns[{index}]"), &pattern.domain_name("", false, 1), config, issues);
+    }
+}
+
+/**
+ * One-shot runs start from an empty state without `state-file`, so missing checks never add up and grace never expires
+ **/
+pub(super) fn validate_persistence(config: &Config, is_daemon: bool, issues: &mut Vec<Issue>) {
+    if !config.unavailable_hide || is_daemon || config.state_file.is_some() {
+        return;
+    }
+    if config.unavailable_checks > 1 {
+        issue(
+            issues,
+            String::from("config.unavailable-checks"),
+            format!("{} needs `state-file` to count missing checks across runs outside daemon mode", config.unavailable_checks)
+        );
+    }
+    if config.unavailable_grace > 0 {
+        issue(
+            issues,
+            String::from("config.unavailable-grace"),
+            format!("{} needs `state-file` to time the grace period across runs outside daemon mode", config.unavailable_grace)
+        );
+    }
+}
+
+/**
+ * `template` rendered for the longest address of the record type, the ownership marker included
+ **/
+fn longest_comment(template: &str, domain_name: &DomainName) -> String {
+    let address = match domain_name.domain_type {
+        DomainType::A => "255.255.255.255",
+        DomainType::AAAA => "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"
+    };
+    use crate::ownership::comment;
+    comment(template, domain_name, address, 0)
+}
+
+fn validate_source(key: &str, domain_name: &DomainName, config: &Config, issues: &mut Vec<Issue>) {
+    if domain_name.source.is_some() && domain_name.provider.is_some() {
+        issue(issues, format!("{key}.provider"), String::from("must not be set together with `source`"));
+    }
+    match domain_name.effective_source() {
+        Source::Provider(provider) if !config.providers.iter().any(|declared| declared.name == provider) => {
+            let key = if domain_name.source.is_some() { format!("{key}.source") } else { format!("{key}.provider") };
+            issue(issues, key, format!("`{provider}` is not declared in config.providers"));
+        }
+        Source::Interface(interface) if interface.is_empty() => {
+            issue(issues, format!("{key}.source"), String::from("interface must not be empty"));
+        }
+        Source::DelegatedPrefix(_) if domain_name.domain_type != DomainType::AAAA || domain_name.ipv6_suffix.is_none() => {
+            issue(issues, format!("{key}.source"), String::from("delegated prefix needs an AAAA record with `ipv6-suffix`"));
+        }
+        Source::Static(address) if !domain_name.domain_type.accepts(&address) => {
+            issue(
+                issues,
+                format!("{key}.source"),
+                format!("static {address} cannot be published as {} record", domain_name.domain_type)
+            );
+        }
+        _ => {}
+    }
+}
+
+fn is_http_url(url: &str) -> bool {
+    use reqwest::Url;
+    Url::parse(url).is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.has_host())
+}
+
+fn is_proxy_url(url: &str) -> bool {
+    use reqwest::Url;
+    Url::parse(url).is_ok_and(|url| Proxy::SCHEMES.contains(&url.scheme()) && url.has_host())
+}
+
+/**
+ * Lowercase without the trailing root dot, `Example.COM.` and `example.com` are the same name
+ **/
+fn normalize(name: &str) -> String {
+    name.trim_end_matches('.').to_ascii_lowercase()
+}
+
+/**
+ * Letters, digits and hyphens, 1 to 63 characters per label without leading or trailing hyphen,
+ * at least two labels and at most 253 characters.
+ * A single leftmost `*` label is accepted for wildcard records.
+ **/
+fn is_fqdn(name: &str) -> bool {
+    if name.is_empty() || name.len() > 253 {
+        return false;
+    }
+    let labels = name.split('.').collect::<Vec<_>>();
+    labels.len() >= 2 && labels.iter().enumerate().all(|(index, label)|
+        (index == 0 && *label == "*") || (
+            !label.is_empty() && label.len() <= 63
+                && !label.starts_with('-') && !label.ends_with('-')
+                && label.chars().all(|char| char.is_ascii_alphanumeric() || char == '-')
+        )
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        super::format::Format,
+        is_fqdn,
+        longest_comment,
+        validate_persistence,
+        validate_references,
+        Cloudflare,
+        Config,
+        Validate
+    };
+
+    #[test]
+    fn test_is_fqdn() {
+        assert!(is_fqdn("example.com"));
+        assert!(is_fqdn("home-1.example.com"));
+        assert!(is_fqdn("*.example.com"));
+        assert!(!is_fqdn("localhost"));
+        assert!(!is_fqdn("-home.example.com"));
+        assert!(!is_fqdn("home..example.com"));
+        assert!(!is_fqdn("home.*.example.com"));
+        assert!(!is_fqdn("home_1.example.com"));
+    }
+
+    #[test]
+    fn test_validate() {
+        let cloudflare: Cloudflare = Format::Json.deserialize(r#"{
+            "zone": "zone",
+            "zone-name": "example.com",
+            "domain-names": [
+                { "name": "home.example.com", "time_to_live": 300 },
+                { "name": "Home.example.com.", "time_to_live": 30 },
+                { "name": "home.example.org", "domain-type": "AAAA" },
+                { "name": "nas.example.com", "domain-type": "AAAA", "ipv6-suffix": "2001:db8::1" }
+            ]
+        }"#).unwrap();
+        let mut issues = Vec::new();
+        cloudflare.validate("cloudflare", &mut issues);
+        let keys = issues.iter().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(
+            keys,
+            [
+                "cloudflare.domain-names[1].time-to-live",
+                "cloudflare.domain-names[1].name",
+                "cloudflare.domain-names[2].name",
+                "cloudflare.domain-names[3].ipv6-suffix"
+            ]
+        );
+
+        let config: Config = Format::Json.deserialize(r#"{
+            "cloudflare-timeout": { "total": 10, "connect": 20, "read": 0 },
+            "cloudflare-proxy": "socks5h://proxy:1080",
+            "ip-sb-proxy": "ftp://proxy"
+        }"#).unwrap();
+        let mut issues = Vec::new();
+        config.validate("config", &mut issues);
+        let keys = issues.iter().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys, ["config.ip-sb-proxy", "config.cloudflare-timeout.read", "config.cloudflare-timeout.connect"]);
+
+        let cloudflare: Cloudflare = Format::Json.deserialize(r#"{
+            "zone": "zone",
+            "domain-names": [
+                { "name": "home.example.com", "provider": "wan1" },
+                { "name": "backup.example.com", "source": { "provider": "wan3" } },
+                { "name": "lan.example.com", "source": { "static": "fd00::1" } },
+                { "name": "nas.example.com", "domain-type": "AAAA", "source": { "static": "fd00::1" }, "ipv6-host-id": "::1:2:3:4" }
+            ]
+        }"#).unwrap();
+        let config: Config = Format::Json.deserialize(r#"{
+            "providers": [{ "name": "wan1", "bind-interface": "eth1" }, { "name": "wan1", "local-address": "192.0.2.2" }]
+        }"#).unwrap();
+        let mut issues = Vec::new();
+        config.validate("config", &mut issues);
+        validate_references(&cloudflare, &config, &mut issues);
+        let keys = issues.iter().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys, ["config.providers[1].name", "cloudflare.domain-names[1].source", "cloudflare.domain-names[2].source"]);
+
+        // Parking needs a placeholder of every family published, AAAA only here
+        let cloudflare: Cloudflare = Format::Json.deserialize(r#"{
+            "zone": "zone",
+            "domain-names": [{ "name": "home.example.com", "domain-type": "AAAA" }]
+        }"#).unwrap();
+        let config: Config = Format::Json.deserialize(r#"{ "unavailable-hide": true, "unavailable-action": "park", "park": { "v4": "192.0.2.1" } }"#)
+            .unwrap();
+        let mut issues = Vec::new();
+        validate_references(&cloudflare, &config, &mut issues);
+        let keys = issues.iter().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys, ["config.park.v6"]);
+    }
+
+    #[test]
+    fn test_validate_comment() {
+        let cloudflare: Cloudflare = Format::Json.deserialize(&format!(r#"{{
+            "zone": "zone",
+            "domain-names": [
+                {{ "name": "home.example.com", "domain-type": "AAAA", "comment": "{{address}} at {{time}}" }},
+                {{ "name": "nas.example.com", "comment": "{}" }},
+                {{ "name": "www.example.com", "comment": "{}" }}
+            ]
+        }}"#, "a".repeat(400), "a".repeat(500))).unwrap();
+        assert_eq!(
+            longest_comment("{address} at {time}", &cloudflare.domain_names[0]),
+            "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff at 1970-01-01T00:00:00Z managed-by=cloudflare_dynamic_dns"
+        );
+        let mut issues = Vec::new();
+        cloudflare.validate("cloudflare", &mut issues);
+        let keys = issues.iter().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        // Past the limit of free plans is left to paid plans
+        assert_eq!(keys, ["cloudflare.domain-names[2].comment"]);
+    }
+
+    #[test]
+    fn test_validate_persistence() {
+        let config: Config = Format::Json.deserialize(r#"{ "unavailable-checks": 3, "unavailable-grace": 600 }"#).unwrap();
+        let mut issues = Vec::new();
+        validate_persistence(&config, true, &mut issues);
+        assert!(issues.is_empty());
+        validate_persistence(&config, false, &mut issues);
+        let keys = issues.iter().map(|issue| issue.key.as_str()).collect::<Vec<_>>();
+        assert_eq!(keys, ["config.unavailable-checks", "config.unavailable-grace"]);
+
+        let config: Config = Format::Json.deserialize(r#"{ "unavailable-checks": 3, "state-file": "/var/lib/cfddns/state.json" }"#).unwrap();
+        let mut issues = Vec::new();
+        validate_persistence(&config, false, &mut issues);
+        assert!(issues.is_empty());
+    }
+
+}
@@ -0,0 +1,206 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration
+};
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+use super::{
+    configuration::{self, cloudflare::domain_name::DomainName, Configuration},
+    notification::Event,
+    ownership,
+    state::State
+};
+
+enum Reload {
+    Hangup,
+    FileChanged(PathBuf)
+}
+
+/**
+ * Check every `interval` seconds until killed.
+ * The configuration is reloaded on SIGHUP or when any file it was read from changes,
+ * a configuration failing to load or validate is discarded and the current one kept.
+ **/
+pub(super) async fn start(mut configuration: Configuration) {
+    let (sender, mut receiver) = unbounded_channel();
+    forward_hangup(sender.clone());
+    // Dropping the watcher stops watching, keep it alive for the whole loop
+    let mut _watcher = watch(&configuration.paths, sender.clone());
+
+    use tokio::time::{interval, MissedTickBehavior};
+    let mut ticker = interval(Duration::from_secs(configuration.config.interval));
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    // Bound once, a changed `metrics-listen` takes a restart
+    if let Some(listen) = configuration.config.metrics_listen {
+        super::metrics::serve(listen).await;
+    }
+
+    // Survives reloads, a changed `state-file` is written from the next check on
+    let mut state = State::load(configuration.config.state_file.as_deref());
+    loop {
+        use tokio::select;
+        select! {
+            _ = ticker.tick() => {
+                // Errors are already logged, simply wait for the next tick
+                let _ = super::cli::run(&configuration, &mut state).await;
+            }
+            Some(reload) = receiver.recv() => {
+                use log::info;
+                match reload {
+                    Reload::Hangup => info!(target: "daemon", "SIGHUP received: Reload configuration"),
+                    Reload::FileChanged(path) => info!(target: "daemon", "{} changed: Reload configuration", path.display())
+                }
+                // Editors tend to emit several events for a single save
+                use tokio::time::sleep;
+                sleep(Duration::from_millis(500)).await;
+                while receiver.try_recv().is_ok() {}
+
+                let Some(reloaded) = accept_reload(&configuration, configuration.reload(), &mut state).await else { continue; };
+                configuration = reloaded;
+                _watcher = watch(&configuration.paths, sender.clone());
+                // A new ticker ticks immediately, reconciling against the new configuration right away
+                ticker = interval(Duration::from_secs(configuration.config.interval));
+                ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            }
+        }
+    }
+}
+
+/**
+ * The configuration to continue with, `None` keeps `current` when `reloaded` failed to load or validate
+ **/
+pub(super) async fn accept_reload(
+    current: &Configuration, reloaded: configuration::Result<Configuration>, state: &mut State
+) -> Option<Configuration> {
+    let configuration = reloaded
+        .map_err(|error| {
+            use log::error;
+            error!(target: "daemon", "Failed to reload configuration, keep the current one: {error}");
+        })
+        .ok()?;
+
+    if configuration.config.cleanup_removed {
+        cleanup_removed(current, &configuration, state).await;
+    }
+    Some(configuration)
+}
+
+/**
+ * Delete records of domain names present in `current` but absent from `reloaded`,
+ * using the credentials of `current` as the zone may have changed as well.
+ * Deletions are notified along with the next check.
+ **/
+async fn cleanup_removed(current: &Configuration, reloaded: &Configuration, state: &mut State) {
+    let is_kept = |domain_name: &DomainName| reloaded.cloudflare.domain_names.iter()
+        .any(|kept| kept.name.eq_ignore_ascii_case(&domain_name.name) && kept.domain_type == domain_name.domain_type)
+        && reloaded.cloudflare.zone == current.cloudflare.zone;
+    let removed = current.cloudflare.domain_names.iter()
+        .filter(|domain_name| !is_kept(domain_name))
+        .collect::<Vec<_>>();
+    if removed.is_empty() {
+        return;
+    }
+
+    let Ok(cloudflare_api) = super::cli::cloudflare_api(current) else {
+        return;
+    };
+    let Ok(records) = super::metrics::cloudflare("list_records", cloudflare_api.list_record().await) else {
+        use log::error;
+        error!(target: "daemon", "Failed to list records for cleaning up removed domain names");
+        return;
+    };
+
+    for domain_name in removed {
+        let record = records.iter()
+            .find(|record| record.domain_name.eq_ignore_ascii_case(&domain_name.name) && record.record_type == domain_name.domain_type);
+        if let Some(record) = record {
+            if !ownership::is_owned(record) && !domain_name.adopt {
+                use log::warn;
+                warn!(target: "daemon", "Keep {} ({}) as it was not created by cloudflare_dynamic_dns", record.domain_name, record.record_type);
+                continue;
+            }
+            use log::info;
+            info!(target: "daemon", "Delete {} ({}) removed from configuration", record.domain_name, record.record_type);
+            let event = Event::Deleted {
+                name: record.domain_name.clone(), record_type: format!("{}", record.record_type), old: record.value.clone()
+            };
+            if !super::cli::approve(&current.config, state, &event).await {
+                continue;
+            }
+            let result = super::cli::handle_record_delete(&cloudflare_api, &record.id).await;
+            super::cli::report(&current.config, state, result, event).await;
+        }
+    }
+}
+
+#[cfg(unix)]
+fn forward_hangup(sender: UnboundedSender<Reload>) {
+    use tokio::signal::unix::{signal, SignalKind};
+    let Ok(mut hangup) = signal(SignalKind::hangup()) else {
+        use log::warn;
+        warn!(target: "daemon", "Failed to listen to SIGHUP, only file changes reload configuration");
+        return;
+    };
+    tokio::spawn(async move {
+        while hangup.recv().await.is_some() {
+            if sender.send(Reload::Hangup).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+#[cfg(not(unix))]
+fn forward_hangup(_: UnboundedSender<Reload>) {}
+
+/**
+ * Watch the directories holding `paths` rather than the files themselves,
+ * so atomic replacement by editors and the `..data` symlink swap of Kubernetes secrets are noticed.
+ **/
+fn watch(paths: &[PathBuf], sender: UnboundedSender<Reload>) -> Option<RecommendedWatcher> {
+    let files = paths.iter()
+        .filter_map(|path| absolute(path))
+        .collect::<Vec<_>>();
+
+    let watched = files.clone();
+    let watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else { return; };
+        if event.kind.is_access() {
+            return;
+        }
+        let changed = event.paths.iter().find(|path|
+            watched.contains(path) || path.file_name().is_some_and(|name| name == "..data")
+        );
+        if let Some(changed) = changed {
+            let _ = sender.send(Reload::FileChanged(changed.to_owned()));
+        }
+    });
+
+    let mut watcher = watcher
+        .map_err(|error| {
+            use log::warn;
+            warn!(target: "daemon", "Failed to watch configuration files, only SIGHUP reloads configuration: {error}");
+        })
+        .ok()?;
+
+    let mut directories = files.iter().filter_map(|file| file.parent()).collect::<Vec<_>>();
+    directories.sort();
+    directories.dedup();
+    for directory in directories {
+        if let Err(error) = watcher.watch(directory, RecursiveMode::NonRecursive) {
+            use log::warn;
+            warn!(target: "daemon", "Failed to watch {}: {error}", directory.display());
+        }
+    }
+    Some(watcher)
+}
+
+fn absolute(path: &Path) -> Option<PathBuf> {
+    let directory = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new(".")
+    };
+    Some(directory.canonicalize().ok()?.join(path.file_name()?))
+}
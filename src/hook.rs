@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+use super::{configuration::config::Config, notification::Event};
+
+/**
+ * Ask `pre-change` whether the change of `event` may go ahead, it is vetoed by a non-zero exit, a timeout or a failure to run
+ **/
+pub async fn approve(config: &Config, event: &Event) -> bool {
+    let Some(command) = &config.pre_change else { return true; };
+    let approved = execute(command, environment(event), config.hook_timeout).await;
+    if !approved {
+        use log::warn;
+        warn!(target: "hook", "Vetoed by pre-change hook: {}", event.text());
+    }
+    approved
+}
+
+/**
+ * Run `on-change` once the change of `event` is done
+ **/
+pub async fn changed(config: &Config, event: &Event) {
+    if let Some(command) = &config.on_change {
+        execute(command, environment(event), config.hook_timeout).await;
+    }
+}
+
+/**
+ * Run `on-error` for a failing check, every failure a line of `CFDDNS_ERROR`
+ **/
+pub async fn failed(config: &Config, failures: &[String]) {
+    if let Some(command) = &config.on_error {
+        let environment = vec![("CFDDNS_ACTION", String::from("error")), ("CFDDNS_ERROR", failures.join("\n"))];
+        execute(command, environment, config.hook_timeout).await;
+    }
+}
+
+/**
+ * `CFDDNS_OLD_IPV4` and the like are named by the family of the address rather than the record type
+ **/
+fn environment(event: &Event) -> Vec<(&'static str, String)> {
+    let (action, name, record_type, old, new) = match event {
+        Event::Created { name, record_type, address } => ("create", name, record_type, None, Some(address)),
+        Event::Changed { name, record_type, old, new } => ("update", name, record_type, Some(old), Some(new)),
+        Event::Deleted { name, record_type, old } => ("delete", name, record_type, Some(old), None),
+        Event::Modified { name, record_type, .. } => ("modify", name, record_type, None, None),
+        Event::Failed { .. } | Event::Recovered => return Vec::new()
+    };
+    let mut environment = vec![("CFDDNS_ACTION", action.to_string()), ("CFDDNS_RECORD", name.clone()), ("CFDDNS_TYPE", record_type.clone())];
+    use std::net::IpAddr;
+    for (address, v4, v6) in [(old, "CFDDNS_OLD_IPV4", "CFDDNS_OLD_IPV6"), (new, "CFDDNS_NEW_IPV4", "CFDDNS_NEW_IPV6")] {
+        if let Some(address) = address {
+            let variable = if address.parse::<IpAddr>().is_ok_and(|address| address.is_ipv6()) { v6 } else { v4 };
+            environment.push((variable, address.clone()));
+        }
+    }
+    if let Event::Modified { old, new, .. } = event {
+        environment.extend([("CFDDNS_OLD", old.clone()), ("CFDDNS_NEW", new.clone())]);
+    }
+    environment
+}
+
+/**
+ * Run `command` through the shell, killed once `timeout` seconds passed, returning whether it exited successfully
+ **/
+async fn execute(command: &str, environment: Vec<(&'static str, String)>, timeout: u64) -> bool {
+    use tokio::process::Command;
+    #[cfg(windows)]
+    let mut process = {
+        let mut process = Command::new("cmd");
+        process.arg("/C").arg(command);
+        process
+    };
+    #[cfg(not(windows))]
+    let mut process = {
+        let mut process = Command::new("sh");
+        process.arg("-c").arg(command);
+        process
+    };
+    process.envs(environment).kill_on_drop(true);
+
+    use log::warn;
+    let mut child = match process.spawn() {
+        Ok(child) => child,
+        Err(error) => {
+            warn!(target: "hook", "Failed to run hook `{command}`: {error}");
+            return false;
+        }
+    };
+    match tokio::time::timeout(Duration::from_secs(timeout), child.wait()).await {
+        Ok(Ok(status)) if status.success() => true,
+        Ok(Ok(status)) => {
+            warn!(target: "hook", "Hook `{command}` exited with {status}");
+            false
+        }
+        Ok(Err(error)) => {
+            warn!(target: "hook", "Failed to wait for hook `{command}`: {error}");
+            false
+        }
+        Err(_) => {
+            warn!(target: "hook", "Hook `{command}` timed out after {timeout} seconds");
+            false
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use super::rest_api::ip_sb::ip::IP;
+
+#[derive(Debug)]
+pub enum Error {
+    /**
+     * Interfaces cannot be enumerated on this platform
+     **/
+    #[cfg_attr(unix, allow(dead_code))]
+    Unsupported,
+    Read,
+    /**
+     * The interface does not exist or holds no usable address
+     **/
+    NoAddress
+}
+
+/**
+ * Addresses currently assigned to the local interface `name`, loopback and link-local ones are skipped.
+ * A global ipv6 address is preferred over a unique local one, temporary ipv6 addresses are skipped where known
+ * so rotating privacy addresses do not rewrite records.
+ **/
+pub fn get_ip(name: &str) -> Result<IP, Error> {
+    let mut addresses = addresses(name)?;
+    if let Some(stable) = stable_v6_addresses(name) {
+        addresses.retain(IpAddr::is_ipv4);
+        addresses.extend(stable.into_iter().map(IpAddr::V6));
+    }
+    let v4 = addresses.iter()
+        .find_map(|address| match address {
+            IpAddr::V4(v4) if is_usable_v4(v4) => Some(v4.to_string()),
+            _ => None
+        });
+    let v6 = addresses.iter()
+        .filter_map(|address| match address {
+            IpAddr::V6(v6) if is_usable_v6(v6) => Some(v6),
+            _ => None
+        })
+        .min_by_key(|v6| is_unique_local(v6))
+        .map(Ipv6Addr::to_string);
+
+    match (v4, v6) {
+        (Some(v4), Some(v6)) => Ok(IP::Both { v4, v6 }),
+        (Some(v4), None) => Ok(IP::V4(v4)),
+        (None, Some(v6)) => Ok(IP::V6(v6)),
+        (None, None) => Err(Error::NoAddress)
+    }
+}
+
+#[cfg(unix)]
+fn addresses(name: &str) -> Result<Vec<IpAddr>, Error> {
+    use nix::ifaddrs::getifaddrs;
+    let addresses = getifaddrs()
+        .map_err(|_| Error::Read)?
+        .filter(|interface_address| interface_address.interface_name == name)
+        .filter_map(|interface_address| interface_address.address)
+        .filter_map(|address| {
+            let v4 = address.as_sockaddr_in().map(|v4| IpAddr::V4(v4.ip()));
+            v4.or_else(|| address.as_sockaddr_in6().map(|v6| IpAddr::V6(v6.ip())))
+        })
+        .collect();
+    Ok(addresses)
+}
+
+#[cfg(not(unix))]
+fn addresses(_: &str) -> Result<Vec<IpAddr>, Error> {
+    Err(Error::Unsupported)
+}
+
+/**
+ * Linux tells temporary and deprecated addresses apart only through `/proc/net/if_inet6`
+ **/
+#[cfg(target_os = "linux")]
+fn stable_v6_addresses(name: &str) -> Option<Vec<Ipv6Addr>> {
+    use std::fs::read_to_string;
+    let if_inet6 = read_to_string("/proc/net/if_inet6").ok()?;
+    use super::prefix::stable_addresses;
+    Some(stable_addresses(&if_inet6, name))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn stable_v6_addresses(_: &str) -> Option<Vec<Ipv6Addr>> {
+    None
+}
+
+fn is_usable_v4(v4: &Ipv4Addr) -> bool {
+    !v4.is_loopback() && !v4.is_link_local() && !v4.is_unspecified()
+}
+
+fn is_usable_v6(v6: &Ipv6Addr) -> bool {
+    !v6.is_loopback() && !v6.is_unicast_link_local() && !v6.is_unspecified() && !v6.is_multicast()
+}
+
+/**
+ * `fc00::/7`
+ **/
+fn is_unique_local(v6: &Ipv6Addr) -> bool {
+    v6.segments()[0] & 0xfe00 == 0xfc00
+}
+
+#[cfg(test)]
+mod test {
+    use super::{get_ip, Error};
+
+    #[test]
+    fn test_loopback_is_skipped() {
+        assert!(matches!(get_ip("lo"), Err(Error::NoAddress)));
+        assert!(matches!(get_ip("cfddns-missing0"), Err(Error::NoAddress)));
+    }
+}
@@ -0,0 +1,44 @@
+/*!
+ * Clients of the Cloudflare DNS record api and of the ip.sb style address echo services,
+ * with the `cloudflare_dynamic_dns` command line tool on top of them.
+ *
+ * The library part lives in [rest_api] and is always built:
+ * - [rest_api::CloudflareApi] lists, creates, updates and deletes the records of one zone
+ * - [rest_api::cloudflare::record::Record] and [rest_api::cloudflare::record::RecordType] are the typed records,
+ *   [rest_api::cloudflare::record::NewRecord] the one to create
+ * - [rest_api::IpSBApi] detects the public IPv4 and IPv6 address
+ * - [rest_api::cloudflare::error::Error] and [rest_api::ip_sb::error::Error] are the errors they return
+ *
+ * Both clients are made by [rest_api::CloudflareApi::builder] and [rest_api::IpSBApi::builder],
+ * which take an existing [reqwest::Client] when given and fail with [rest_api::builder::Error] instead of panicking.
+ *
+ * Everything else is the command line tool, built with the default `cli` feature.
+ * Library users turn it off with `default-features = false` to skip its dependencies.
+ **/
+pub mod rest_api;
+#[cfg(feature = "cli")]
+pub mod cli;
+#[cfg(feature = "cli")]
+mod configuration;
+#[cfg(feature = "cli")]
+mod daemon;
+#[cfg(feature = "cli")]
+mod hook;
+#[cfg(feature = "cli")]
+mod interface;
+#[cfg(feature = "cli")]
+mod metrics;
+#[cfg(feature = "cli")]
+mod notification;
+#[cfg(feature = "cli")]
+mod ownership;
+#[cfg(feature = "cli")]
+mod prefix;
+#[cfg(feature = "cli")]
+mod state;
+#[cfg(feature = "cli")]
+mod status;
+#[cfg(feature = "cli")]
+mod record_types;
+#[cfg(all(test, feature = "cli"))]
+mod test;
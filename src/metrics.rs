@@ -0,0 +1,301 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    net::SocketAddr,
+    sync::Mutex,
+    time::Duration
+};
+
+use super::{notification::Event, rest_api::cloudflare::error::Error};
+
+/**
+ * Upper bounds in seconds of the provider latency buckets
+ **/
+const BUCKETS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/**
+ * Everything `/metrics` exposes, collected whether or not it is served
+ **/
+struct Metrics {
+    last_success: Option<u64>,
+    healthy: bool,
+    /**
+     * Detected address keyed by source and family
+     **/
+    addresses: BTreeMap<(String, &'static str), String>,
+    ip_changes: u64,
+    /**
+     * Keyed by endpoint and `success` or `failure`
+     **/
+    cloudflare_requests: BTreeMap<(&'static str, &'static str), u64>,
+    /**
+     * Keyed by endpoint and error variant
+     **/
+    cloudflare_errors: BTreeMap<(&'static str, String), u64>,
+    provider_latency: BTreeMap<String, Histogram>,
+    /**
+     * Unix time of the last change keyed by name and type
+     **/
+    record_updates: BTreeMap<(String, String), u64>
+}
+
+#[derive(Default)]
+struct Histogram {
+    buckets: [u64; BUCKETS.len()],
+    sum: f64,
+    count: u64
+}
+
+impl Metrics {
+    const fn new() -> Self {
+        Self {
+            last_success: None,
+            healthy: true,
+            addresses: BTreeMap::new(),
+            ip_changes: 0,
+            cloudflare_requests: BTreeMap::new(),
+            cloudflare_errors: BTreeMap::new(),
+            provider_latency: BTreeMap::new(),
+            record_updates: BTreeMap::new()
+        }
+    }
+
+    fn checked(&mut self, succeeded: bool, now: u64, failed_checks: u32, threshold: u32) {
+        if succeeded {
+            self.last_success = Some(now);
+        }
+        self.healthy = failed_checks < threshold;
+    }
+}
+
+static METRICS: Mutex<Metrics> = Mutex::new(Metrics::new());
+
+fn with<T>(f: impl FnOnce(&mut Metrics) -> T) -> T {
+    lock(&METRICS, f)
+}
+
+fn lock<T>(metrics: &Mutex<Metrics>, f: impl FnOnce(&mut Metrics) -> T) -> T {
+    // A panic while holding the lock leaves the metrics consistent enough to keep serving
+    f(&mut metrics.lock().unwrap_or_else(|poisoned| poisoned.into_inner()))
+}
+
+/**
+ * Count a Cloudflare api request of `endpoint`, passing its result through
+ **/
+pub fn cloudflare<T>(endpoint: &'static str, result: Result<T, Error>) -> Result<T, Error> {
+    with(|metrics| {
+        let status = if result.is_ok() { "success" } else { "failure" };
+        *metrics.cloudflare_requests.entry((endpoint, status)).or_default() += 1;
+        if let Err(error) = &result {
+            *metrics.cloudflare_errors.entry((endpoint, format!("{error:?}"))).or_default() += 1;
+        }
+    });
+    result
+}
+
+/**
+ * Observe how long `provider` took to detect `addresses`, failed detections included
+ **/
+pub fn detected(provider: &str, elapsed: Duration) {
+    with(|metrics| {
+        let histogram = metrics.provider_latency.entry(provider.to_string()).or_default();
+        let seconds = elapsed.as_secs_f64();
+        for (bucket, bound) in histogram.buckets.iter_mut().zip(BUCKETS) {
+            if seconds <= bound {
+                *bucket += 1;
+            }
+        }
+        histogram.sum += seconds;
+        histogram.count += 1;
+    });
+}
+
+pub fn address(source: &str, v4: Option<String>, v6: Option<String>) {
+    with(|metrics| {
+        for (family, address) in [("v4", v4), ("v6", v6)] {
+            let key = (source.to_string(), family);
+            match address {
+                Some(address) => metrics.addresses.insert(key, address),
+                None => metrics.addresses.remove(&key)
+            };
+        }
+    });
+}
+
+pub fn changed(event: &Event, now: u64) {
+    with(|metrics| {
+        let (name, record_type) = match event {
+            Event::Created { name, record_type, .. } | Event::Modified { name, record_type, .. } | Event::Deleted { name, record_type, .. } =>
+                (name, record_type),
+            Event::Changed { name, record_type, .. } => {
+                metrics.ip_changes += 1;
+                (name, record_type)
+            }
+            Event::Failed { .. } | Event::Recovered => return
+        };
+        metrics.record_updates.insert((name.clone(), record_type.clone()), now);
+    });
+}
+
+/**
+ * Conclude a check, `/healthz` fails while `failed_checks` reaches `threshold`
+ **/
+pub fn checked(succeeded: bool, now: u64, failed_checks: u32, threshold: u32) {
+    with(|metrics| metrics.checked(succeeded, now, failed_checks, threshold));
+}
+
+/**
+ * Prometheus text exposition format
+ **/
+fn render(metrics: &Mutex<Metrics>) -> String {
+    lock(metrics, |metrics| {
+        let mut text = String::new();
+        let mut family = |name: &str, kind: &str, help: &str, samples: Vec<String>| {
+            let _ = writeln!(text, "# HELP {name} {help}\n# TYPE {name} {kind}");
+            for sample in samples {
+                let _ = writeln!(text, "{sample}");
+            }
+        };
+
+        family(
+            "cfddns_last_success_timestamp_seconds", "gauge", "Unix time of the last check without failure",
+            metrics.last_success.iter().map(|last_success| format!("cfddns_last_success_timestamp_seconds {last_success}")).collect()
+        );
+        family(
+            "cfddns_ip_info", "gauge", "Address currently detected by each source",
+            metrics.addresses.iter()
+                .map(|((source, family), address)|
+                    format!("cfddns_ip_info{{source=\"{}\",family=\"{family}\",address=\"{}\"}} 1", escape(source), escape(address))
+                )
+                .collect()
+        );
+        family(
+            "cfddns_ip_changes_total", "counter", "Records updated to a new address",
+            vec![format!("cfddns_ip_changes_total {}", metrics.ip_changes)]
+        );
+        family(
+            "cfddns_cloudflare_requests_total", "counter", "Cloudflare api requests by endpoint and status",
+            metrics.cloudflare_requests.iter()
+                .map(|((endpoint, status), count)| format!("cfddns_cloudflare_requests_total{{endpoint=\"{endpoint}\",status=\"{status}\"}} {count}"))
+                .collect()
+        );
+        family(
+            "cfddns_cloudflare_errors_total", "counter", "Failed Cloudflare api requests by endpoint and error",
+            metrics.cloudflare_errors.iter()
+                .map(|((endpoint, error), count)| format!("cfddns_cloudflare_errors_total{{endpoint=\"{endpoint}\",error=\"{error}\"}} {count}"))
+                .collect()
+        );
+        family(
+            "cfddns_provider_latency_seconds", "histogram", "Duration of address detections by provider",
+            metrics.provider_latency.iter()
+                .flat_map(|(provider, histogram)| {
+                    let provider = escape(provider);
+                    BUCKETS.iter().map(|bound| bound.to_string()).chain([String::from("+Inf")])
+                        .zip(histogram.buckets.into_iter().chain([histogram.count]))
+                        .map(|(bound, count)| format!("cfddns_provider_latency_seconds_bucket{{provider=\"{provider}\",le=\"{bound}\"}} {count}"))
+                        .chain([
+                            format!("cfddns_provider_latency_seconds_sum{{provider=\"{provider}\"}} {}", histogram.sum),
+                            format!("cfddns_provider_latency_seconds_count{{provider=\"{provider}\"}} {}", histogram.count)
+                        ])
+                        .collect::<Vec<_>>()
+                })
+                .collect()
+        );
+        family(
+            "cfddns_record_last_update_timestamp_seconds", "gauge", "Unix time of the last change of each record",
+            metrics.record_updates.iter()
+                .map(|((name, record_type), updated)|
+                    format!("cfddns_record_last_update_timestamp_seconds{{name=\"{}\",type=\"{record_type}\"}} {updated}", escape(name))
+                )
+                .collect()
+        );
+        text
+    })
+}
+
+pub fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/**
+ * Serve `/metrics` and `/healthz` on `listen` in the background, for as long as the daemon runs.
+ * The bound address is returned, the port picked when `listen` asks for port 0.
+ **/
+pub async fn serve(listen: SocketAddr) -> Option<SocketAddr> {
+    serve_metrics(listen, &METRICS).await
+}
+
+async fn serve_metrics(listen: SocketAddr, metrics: &'static Mutex<Metrics>) -> Option<SocketAddr> {
+    use axum::{http::StatusCode, routing::get, Router};
+    let router = Router::new()
+        .route("/metrics", get(|| async {
+            ([("Content-Type", "text/plain; version=0.0.4")], render(metrics))
+        }))
+        .route("/healthz", get(|| async {
+            if lock(metrics, |metrics| metrics.healthy) { (StatusCode::OK, "ok") } else { (StatusCode::SERVICE_UNAVAILABLE, "failing") }
+        }));
+
+    use tokio::net::TcpListener;
+    use log::error;
+    let listener = match TcpListener::bind(listen).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            error!(target: "metrics", "Failed to listen on {listen}: {error}");
+            return None;
+        }
+    };
+    let address = listener.local_addr().unwrap_or(listen);
+    use log::info;
+    info!(target: "metrics", "Serve /metrics and /healthz on {address}");
+    tokio::spawn(async move {
+        if let Err(error) = axum::serve(listener, router).await {
+            error!(target: "metrics", "Metrics server stopped: {error}");
+        }
+    });
+    Some(address)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::{detected, lock, render, serve_metrics, Metrics, METRICS};
+
+    #[test]
+    fn test_histogram() {
+        detected("test-histogram", Duration::from_millis(300));
+        let text = render(&METRICS);
+        assert!(text.contains(r#"cfddns_provider_latency_seconds_bucket{provider="test-histogram",le="0.25"} 0"#));
+        assert!(text.contains(r#"cfddns_provider_latency_seconds_bucket{provider="test-histogram",le="0.5"} 1"#));
+        assert!(text.contains(r#"cfddns_provider_latency_seconds_count{provider="test-histogram"} 1"#));
+    }
+
+    #[tokio::test]
+    async fn test_serve() {
+        // Apart from the global metrics, which checks of other tests keep changing
+        use std::sync::Mutex;
+        static SERVED: Mutex<Metrics> = Mutex::new(Metrics::new());
+        let address = serve_metrics("127.0.0.1:0".parse().unwrap(), &SERVED).await.unwrap();
+        lock(&SERVED, |metrics| {
+            metrics.ip_changes = 2;
+            metrics.checked(true, 1700000000, 0, 2);
+        });
+
+        use reqwest::{get, StatusCode};
+        let response = get(format!("http://{address}/metrics")).await.unwrap();
+        assert_eq!(response.headers()["content-type"], "text/plain; version=0.0.4");
+        let text = response.text().await.unwrap();
+        assert!(text.contains("# TYPE cfddns_ip_changes_total counter\ncfddns_ip_changes_total 2\n"));
+        assert!(text.contains("cfddns_last_success_timestamp_seconds 1700000000\n"));
+
+        let healthz = format!("http://{address}/healthz");
+        assert_eq!(get(&healthz).await.unwrap().status(), StatusCode::OK);
+        lock(&SERVED, |metrics| metrics.checked(false, 1700000060, 1, 2));
+        assert_eq!(get(&healthz).await.unwrap().status(), StatusCode::OK);
+        // Failing once `healthz-failures` consecutive checks failed
+        lock(&SERVED, |metrics| metrics.checked(false, 1700000120, 2, 2));
+        assert_eq!(get(&healthz).await.unwrap().status(), StatusCode::SERVICE_UNAVAILABLE);
+        lock(&SERVED, |metrics| metrics.checked(true, 1700000180, 0, 2));
+        assert_eq!(get(&healthz).await.unwrap().status(), StatusCode::OK);
+    }
+}
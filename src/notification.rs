@@ -0,0 +1,251 @@
+mod email;
+
+use super::configuration::{
+    config::notification::{EventKind, Notification, Preset},
+    Configuration
+};
+
+/**
+ * Something worth telling the webhooks in `notifications` and `smtp` about
+ **/
+#[derive(Clone, PartialEq, Debug)]
+pub enum Event {
+    Created { name: String, record_type: String, address: String },
+    Changed { name: String, record_type: String, old: String, new: String },
+    /**
+     * Proxied state, comment, tags or ttl rewritten with the address kept, `old` and `new` read like `proxied=false`
+     **/
+    Modified { name: String, record_type: String, old: String, new: String },
+    Deleted { name: String, record_type: String, old: String },
+    Failed { messages: Vec<String> },
+    Recovered
+}
+
+impl Event {
+
+    pub fn kind(&self) -> EventKind {
+        match self {
+            Event::Created { .. } => EventKind::Created,
+            Event::Changed { .. } | Event::Modified { .. } => EventKind::Changed,
+            Event::Deleted { .. } => EventKind::Deleted,
+            Event::Failed { .. } => EventKind::Failed,
+            Event::Recovered => EventKind::Recovered
+        }
+    }
+
+    /**
+     * Name and type of the record changed
+     **/
+    pub fn record(&self) -> Option<(&str, &str)> {
+        match self {
+            Event::Created { name, record_type, .. } | Event::Changed { name, record_type, .. }
+            | Event::Modified { name, record_type, .. } | Event::Deleted { name, record_type, .. } => Some((name, record_type)),
+            Event::Failed { .. } | Event::Recovered => None
+        }
+    }
+
+    /**
+     * One line summary, the message of presets
+     **/
+    pub fn text(&self) -> String {
+        match self {
+            Event::Created { name, record_type, address } => format!("Create {name} ({record_type}) -> {address}"),
+            Event::Changed { name, record_type, old, new } => format!("Update {name} ({record_type}) {old} -> {new}"),
+            Event::Modified { name, record_type, old, new } => format!("Modify {name} ({record_type}) {old} -> {new}"),
+            Event::Deleted { name, record_type, old } => format!("Delete {name} ({record_type}) {old}"),
+            Event::Failed { messages } => format!("Check failed: {}", messages.join("; ")),
+            Event::Recovered => String::from("Check recovered")
+        }
+    }
+
+    /**
+     * Values of the template placeholders, empty when not applicable
+     **/
+    fn fields(&self) -> [(&'static str, String); 7] {
+        let (name, record_type, old, new, message) = match self {
+            Event::Created { name, record_type, address } => (name.as_str(), record_type.as_str(), "", address.as_str(), String::new()),
+            Event::Changed { name, record_type, old, new } | Event::Modified { name, record_type, old, new } =>
+                (name.as_str(), record_type.as_str(), old.as_str(), new.as_str(), String::new()),
+            Event::Deleted { name, record_type, old } => (name.as_str(), record_type.as_str(), old.as_str(), "", String::new()),
+            Event::Failed { messages } => ("", "", "", "", messages.join("; ")),
+            Event::Recovered => ("", "", "", "", String::new())
+        };
+        use serde_json::to_value;
+        let event = to_value(self.kind()).ok().and_then(|kind| kind.as_str().map(str::to_string)).unwrap_or_default();
+        [
+            ("event", event),
+            ("name", name.to_string()),
+            ("type", record_type.to_string()),
+            ("old", old.to_string()),
+            ("new", new.to_string()),
+            ("message", message),
+            ("text", self.text())
+        ]
+    }
+
+}
+
+/**
+ * Send pending events of `state` along with failure and recovery of the check.
+ * A failure is notified once after `notify-failures-after` consecutive failing checks until recovered.
+ **/
+pub async fn notify(configuration: &Configuration, state: &mut super::state::State) {
+    let mut events = std::mem::take(&mut state.events);
+    let failures = std::mem::take(&mut state.failures);
+    if failures.is_empty() {
+        if state.failure_notified {
+            events.push(Event::Recovered);
+        }
+        state.failed_checks = 0;
+        state.failure_notified = false;
+    }
+    else {
+        state.failed_checks += 1;
+        if state.failed_checks >= configuration.config.notify_failures_after && !state.failure_notified {
+            events.push(Event::Failed { messages: failures });
+            state.failure_notified = true;
+        }
+    }
+
+    if events.is_empty() {
+        return;
+    }
+
+    let notifications = &configuration.config.notifications;
+    if !notifications.is_empty() && let Some(client) = client(configuration) {
+        for notification in notifications {
+            for event in events.iter().filter(|event| notification.events.is_empty() || notification.events.contains(&event.kind())) {
+                send(&client, notification, event).await;
+            }
+        }
+    }
+
+    if let Some(smtp) = &configuration.config.smtp {
+        let events = events.iter()
+            .filter(|event| smtp.events.is_empty() || smtp.events.contains(&event.kind()))
+            .collect::<Vec<_>>();
+        if !events.is_empty() {
+            email::send(smtp, &events).await;
+        }
+    }
+}
+
+/**
+ * Webhooks leave through the same proxy and timeouts as the Cloudflare api
+ **/
+fn client(configuration: &Configuration) -> Option<reqwest::Client> {
+    use reqwest::Client;
+    let (total, connect, read) = configuration.config.cloudflare_timeout.durations();
+    let builder = Client::builder()
+        .timeout(total)
+        .connect_timeout(connect)
+        .read_timeout(read);
+    configuration.config.cloudflare_proxy.apply(builder)
+        .ok()
+        .and_then(|builder| builder.build().ok())
+        .or_else(|| {
+            use log::error;
+            error!(target: "notification", "Error occurred when building reqwest client, webhooks are not sent");
+            None
+        })
+}
+
+async fn send(client: &reqwest::Client, notification: &Notification, event: &Event) {
+    let (content_type, body) = body(notification, event);
+    let mut request = client.post(&notification.url)
+        .header("Content-Type", content_type)
+        .body(body);
+    if notification.preset == Preset::Ntfy {
+        request = request.header("Title", "cloudflare_dynamic_dns");
+    }
+    for (name, value) in &notification.headers {
+        request = request.header(name, value);
+    }
+
+    use log::warn;
+    match request.send().await.and_then(|response| response.error_for_status()) {
+        Ok(_) => {
+            use log::debug;
+            debug!(target: "notification", "Notified {:?} of {:?}", notification.preset, event.kind());
+        }
+        Err(error) => warn!(target: "notification", "Failed to notify {:?} of {:?}: {error}", notification.preset, event.kind())
+    }
+}
+
+/**
+ * Content type and body expected by the preset
+ **/
+fn body(notification: &Notification, event: &Event) -> (&'static str, String) {
+    use serde_json::json;
+    let text = notification.template.as_deref()
+        .map(|template| render(template, event, false))
+        .unwrap_or_else(|| event.text());
+    match notification.preset {
+        Preset::Generic => {
+            let body = match &notification.template {
+                Some(template) => render(template, event, true),
+                None => serde_json::Value::Object(
+                    event.fields().into_iter().map(|(key, value)| (key.to_string(), value.into())).collect()
+                ).to_string()
+            };
+            ("application/json", body)
+        }
+        Preset::Slack => ("application/json", json!({ "text": text }).to_string()),
+        Preset::Discord => ("application/json", json!({ "content": text }).to_string()),
+        Preset::Telegram => ("application/json", json!({ "chat_id": notification.chat_id, "text": text }).to_string()),
+        Preset::Ntfy => ("text/plain", text),
+        Preset::Gotify => ("application/json", json!({ "title": "cloudflare_dynamic_dns", "message": text, "priority": 5 }).to_string())
+    }
+}
+
+/**
+ * Replace the `{placeholder}`s of `template`, values are escaped to sit inside a json string when `escape`
+ **/
+fn render(template: &str, event: &Event, escape: bool) -> String {
+    event.fields().into_iter().fold(template.to_string(), |rendered, (key, value)| {
+        let value = if escape {
+            let quoted = serde_json::Value::String(value).to_string();
+            quoted[1..quoted.len() - 1].to_string()
+        }
+        else {
+            value
+        };
+        rendered.replace(&format!("{{{key}}}"), &value)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeMap;
+
+    use super::{body, Event, Notification, Preset};
+
+    fn notification(preset: Preset, template: Option<&str>) -> Notification {
+        Notification {
+            preset,
+            url: String::from("http://127.0.0.1/hook"),
+            chat_id: Some(String::from("42")),
+            events: Vec::new(),
+            template: template.map(str::to_string),
+            headers: BTreeMap::new()
+        }
+    }
+
+    #[test]
+    fn test_body() {
+        let event = Event::Changed {
+            name: String::from("home.example.com"),
+            record_type: String::from("A"),
+            old: String::from("203.0.113.1"),
+            new: String::from("198.51.100.7")
+        };
+        let text = "Update home.example.com (A) 203.0.113.1 -> 198.51.100.7";
+        assert_eq!(body(&notification(Preset::Slack, None), &event).1, format!(r#"{{"text":"{text}"}}"#));
+        assert_eq!(body(&notification(Preset::Telegram, None), &event).1, format!(r#"{{"chat_id":"42","text":"{text}"}}"#));
+        assert_eq!(body(&notification(Preset::Ntfy, Some("{name} is {new}")), &event), ("text/plain", String::from("home.example.com is 198.51.100.7")));
+
+        let failed = Event::Failed { messages: vec![String::from(r#"say "hi""#)] };
+        let generic = notification(Preset::Generic, Some(r#"{"alert": "{message}"}"#));
+        assert_eq!(body(&generic, &failed).1, r#"{"alert": "say \"hi\""}"#);
+    }
+}
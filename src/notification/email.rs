@@ -0,0 +1,71 @@
+use lettre::Message;
+
+use crate::configuration::config::smtp::{Security, Smtp};
+use super::Event;
+
+/**
+ * Mail one summary of `events` to every recipient of `smtp`
+ **/
+pub(super) async fn send(smtp: &Smtp, events: &[&Event]) {
+    use log::warn;
+    let Some(message) = message(smtp, events) else {
+        warn!(target: "notification", "Failed to build mail to {}", smtp.to.join(", "));
+        return;
+    };
+
+    use lettre::{
+        transport::smtp::{authentication::Credentials, client::{Tls, TlsParameters}},
+        AsyncSmtpTransport,
+        AsyncTransport,
+        Tokio1Executor
+    };
+    let tls = match smtp.security {
+        Security::None => Ok(Tls::None),
+        Security::Starttls => TlsParameters::new(smtp.host.clone()).map(Tls::Required),
+        Security::Tls => TlsParameters::new(smtp.host.clone()).map(Tls::Wrapper)
+    };
+    let tls = match tls {
+        Ok(tls) => tls,
+        Err(error) => {
+            warn!(target: "notification", "Failed to set up tls to {}: {error}", smtp.host);
+            return;
+        }
+    };
+
+    use std::time::Duration;
+    let mut builder = AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp.host)
+        .port(smtp.port())
+        .tls(tls)
+        .timeout(Some(Duration::from_secs(smtp.timeout)));
+    if let (Some(username), Some(password)) = (&smtp.username, &smtp.password) {
+        builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+
+    match builder.build().send(message).await {
+        Ok(_) => {
+            use log::debug;
+            debug!(target: "notification", "Mailed {} events to {}", events.len(), smtp.to.join(", "));
+        }
+        Err(error) => warn!(target: "notification", "Failed to mail {}: {error}", smtp.to.join(", "))
+    }
+}
+
+/**
+ * A single event is its own subject, several are counted and listed in the body
+ **/
+fn message(smtp: &Smtp, events: &[&Event]) -> Option<Message> {
+    let subject = match events {
+        [event] => event.text(),
+        _ => format!("{} events", events.len())
+    };
+    let body = events.iter().map(|event| event.text()).collect::<Vec<_>>().join("\n");
+
+    let mut builder = Message::builder()
+        .from(smtp.from.parse().ok()?)
+        .subject(format!("cloudflare_dynamic_dns: {subject}"));
+    for to in &smtp.to {
+        builder = builder.to(to.parse().ok()?);
+    }
+    use lettre::message::header::ContentType;
+    builder.header(ContentType::TEXT_PLAIN).body(body).ok()
+}
@@ -0,0 +1,84 @@
+use super::{
+    configuration::cloudflare::domain_name::{source::Source, DomainName},
+    rest_api::cloudflare::record::Record
+};
+
+/**
+ * Written into the comment of every record this tool creates or adopts,
+ * records without it were made by hand or by another tool and are never overwritten or deleted unasked
+ **/
+pub const MARKER: &str = "managed-by=cloudflare_dynamic_dns";
+
+pub fn is_owned(record: &Record) -> bool {
+    record.comment.as_deref().is_some_and(|comment| comment.contains(MARKER))
+}
+
+/**
+ * `comment` with the marker appended, an existing comment is kept
+ **/
+pub fn mark(comment: Option<&str>) -> String {
+    match comment.map(str::trim) {
+        Some(comment) if comment.contains(MARKER) => comment.to_string(),
+        Some(comment) if !comment.is_empty() => format!("{comment} {MARKER}"),
+        _ => MARKER.to_string()
+    }
+}
+
+/**
+ * The `comment` template of `domain_name` with `{name}`, `{address}`, `{provider}` and `{time}` replaced, marked
+ **/
+pub fn comment(template: &str, domain_name: &DomainName, address: &str, now: u64) -> String {
+    let provider = match domain_name.effective_source() {
+        Source::Provider(provider) => provider,
+        source => source.to_string()
+    };
+    let comment = template
+        .replace("{name}", &domain_name.name)
+        .replace("{address}", address)
+        .replace("{provider}", &provider)
+        .replace("{time}", &rfc3339(now));
+    mark(Some(&comment))
+}
+
+/**
+ * A comment rendering the time changes every check, it is only rewritten along with the address
+ **/
+pub fn is_volatile(template: &str) -> bool {
+    template.contains("{time}")
+}
+
+/**
+ * Unix time as `1970-01-01T00:00:00Z`, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+ **/
+fn rfc3339(time: u64) -> String {
+    let (days, seconds) = (time / 86400, time % 86400);
+    let days = days as i64 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_shifted = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_shifted + 2) / 5 + 1;
+    let month = if month_shifted < 10 { month_shifted + 3 } else { month_shifted - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z", seconds / 3600, seconds % 3600 / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{mark, rfc3339, MARKER};
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(951782400), "2000-02-29T00:00:00Z");
+        assert_eq!(rfc3339(1790000000), "2026-09-21T14:13:20Z");
+    }
+
+    #[test]
+    fn test_mark() {
+        assert_eq!(mark(None), MARKER);
+        assert_eq!(mark(Some("nas")), format!("nas {MARKER}"));
+        assert_eq!(mark(Some(&mark(Some("nas")))), format!("nas {MARKER}"));
+    }
+}
@@ -0,0 +1,112 @@
+use std::net::Ipv6Addr;
+
+/**
+ * Length of the prefix kept from a detected address, the interface identifier fills the rest
+ **/
+pub const PREFIX_LENGTH: u32 = 64;
+
+/**
+ * The /64 prefix of `address` followed by the interface identifier of `suffix`,
+ * e.g. `2001:db8:1:2::abcd` with `::1234:5678:9abc:def0` gives `2001:db8:1:2:1234:5678:9abc:def0`
+ **/
+pub fn combine(address: &Ipv6Addr, suffix: &Ipv6Addr) -> Ipv6Addr {
+    let mask = u128::MAX << (128 - PREFIX_LENGTH);
+    Ipv6Addr::from((address.to_bits() & mask) | (suffix.to_bits() & !mask))
+}
+
+/**
+ * Whether `suffix` only holds an interface identifier, nothing within the prefix
+ **/
+pub fn is_host_id(suffix: &Ipv6Addr) -> bool {
+    suffix.to_bits() >> (128 - PREFIX_LENGTH) == 0
+}
+
+/**
+ * `IFA_F_TEMPORARY`, `IFA_F_DADFAILED`, `IFA_F_DEPRECATED` and `IFA_F_TENTATIVE` of `linux/if_addr.h`
+ **/
+const UNSTABLE_FLAGS: u32 = 0x01 | 0x08 | 0x20 | 0x40;
+
+/**
+ * Addresses of `interface` listed in `/proc/net/if_inet6` content, leaving out SLAAC privacy addresses
+ * which rotate every few hours and addresses not usable yet or anymore
+ **/
+pub fn stable_addresses(if_inet6: &str, interface: &str) -> Vec<Ipv6Addr> {
+    if_inet6.lines()
+        .filter_map(|line| {
+            // address, interface index, prefix length, scope, flags, interface name
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [address, _, _, _, flags, name] = fields[..] else { return None; };
+            if name != interface || u32::from_str_radix(flags, 16).ok()? & UNSTABLE_FLAGS != 0 {
+                return None;
+            }
+            u128::from_str_radix(address, 16).ok().map(Ipv6Addr::from)
+        })
+        .collect()
+}
+
+/**
+ * The delegated prefix found in a DHCPv6-PD lease, its first /64 is returned.
+ * Understands `2001:db8:1::/56` notation as in odhcp6c `PREFIXES=` and
+ * dhcpcd `dhcp6_ia_pd1_prefix1=2001:db8:1::` with `dhcp6_ia_pd1_prefix1_length=56`.
+ **/
+pub fn delegated_prefix(lease: &str) -> Option<Ipv6Addr> {
+    let is_prefix = |address: &Ipv6Addr, length: u32| {
+        length <= PREFIX_LENGTH && !address.is_loopback() && !address.is_unicast_link_local() && !address.is_unspecified()
+    };
+    let mut tokens = lease.split(|char: char| char.is_whitespace() || matches!(char, ',' | ';' | '\'' | '"'));
+    let notation = tokens.find_map(|token| {
+        let token = token.rsplit('=').next()?;
+        let (address, length) = token.split_once('/')?;
+        let (address, length) = (address.parse::<Ipv6Addr>().ok()?, length.parse::<u32>().ok()?);
+        is_prefix(&address, length).then_some(address)
+    });
+    notation.or_else(|| lease.lines().find_map(|line| {
+        let (key, address) = line.trim().split_once('=')?;
+        if !key.contains("prefix") || key.ends_with("_length") {
+            return None;
+        }
+        let address = address.trim_matches(|char| char == '\'' || char == '"').parse::<Ipv6Addr>().ok()?;
+        let length = lease.lines()
+            .find_map(|line| line.trim().strip_prefix(&format!("{key}_length=")))
+            .and_then(|length| length.trim_matches(|char| char == '\'' || char == '"').parse::<u32>().ok())
+            .unwrap_or(PREFIX_LENGTH);
+        is_prefix(&address, length).then_some(address)
+    }))
+    .map(|address| combine(&address, &Ipv6Addr::UNSPECIFIED))
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::Ipv6Addr;
+
+    use super::{combine, delegated_prefix, is_host_id, stable_addresses};
+
+    #[test]
+    fn test_combine() {
+        let (address, suffix) = ("2001:db8:1:2::abcd".parse::<Ipv6Addr>().unwrap(), "::1234:5678:9abc:def0".parse().unwrap());
+        assert_eq!(combine(&address, &suffix), "2001:db8:1:2:1234:5678:9abc:def0".parse::<Ipv6Addr>().unwrap());
+        assert!(is_host_id(&suffix));
+        assert!(!is_host_id(&address));
+    }
+
+    #[test]
+    fn test_stable_addresses() {
+        let if_inet6 = concat!(
+            "20010db8000100020000000000000001 02 40 00 00     eth0\n",
+            "20010db800010002a1b2c3d4e5f60718 02 40 00 01     eth0\n",
+            "20010db800010002aaaaaaaaaaaaaaaa 02 40 00 20     eth0\n",
+            "fe80000000000000021122fffe334455 02 40 20 80     eth0\n",
+            "20010db8000900020000000000000001 03 40 00 00     eth1\n"
+        );
+        let addresses = stable_addresses(if_inet6, "eth0");
+        assert_eq!(addresses, ["2001:db8:1:2::1".parse::<Ipv6Addr>().unwrap(), "fe80::211:22ff:fe33:4455".parse().unwrap()]);
+    }
+
+    #[test]
+    fn test_delegated_prefix() {
+        let expected = "2001:db8:1::".parse::<Ipv6Addr>().unwrap();
+        assert_eq!(delegated_prefix("PREFIXES='2001:db8:1::/56,3600,7200'"), Some(expected));
+        assert_eq!(delegated_prefix("dhcp6_ia_pd1_prefix1_length='56'\ndhcp6_ia_pd1_prefix1='2001:db8:1::'\n"), Some(expected));
+        assert_eq!(delegated_prefix("RA_ADDRESSES=fe80::1/64"), None);
+    }
+}
@@ -1,9 +1,9 @@
 use crate::configuration::cloudflare::domain_name::DomainType;
 use crate::rest_api::cloudflare::record::RecordType;
 
-impl Into<RecordType> for DomainType {
-    fn into(self) -> RecordType {
-        match self {
+impl From<DomainType> for RecordType {
+    fn from(domain_type: DomainType) -> Self {
+        match domain_type {
             DomainType::A => RecordType::A,
             DomainType::AAAA => RecordType::AAAA
         }
@@ -1,13 +1,32 @@
-use reqwest::Client;
+use reqwest::{header::HeaderValue, Client};
 
+pub mod builder;
 pub mod cloudflare;
 pub mod ip_sb;
+mod binding;
+mod proxy;
+#[cfg(test)]
+pub(crate) mod mock;
 
+pub use binding::{Binding, Family};
+pub use proxy::Proxy;
+
+/**
+ * Detects the public addresses of this host through echo services answering `{"ip": "..."}`
+ **/
 pub struct IpSBApi {
-    client: Client
+    v4_client: Option<Client>,
+    v6_client: Option<Client>,
+    v4_url: String,
+    v6_url: String
 }
 
+/**
+ * Client of the dns records of one zone, authenticated by an api token
+ **/
 pub struct CloudflareApi {
     client: Client,
+    authorization: HeaderValue,
+    base_url: String,
     zone: String
 }
\ No newline at end of file
@@ -0,0 +1,86 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use reqwest::ClientBuilder;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Family {
+    V4,
+    V6
+}
+
+impl Family {
+    pub fn matches(self, address: &IpAddr) -> bool {
+        matches!((self, address), (Self::V4, IpAddr::V4(_)) | (Self::V6, IpAddr::V6(_)))
+    }
+
+    fn unspecified(self) -> IpAddr {
+        match self {
+            Self::V4 => IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            Self::V6 => IpAddr::V6(Ipv6Addr::UNSPECIFIED)
+        }
+    }
+}
+
+/**
+ * Where the connections of a client leave from, choosing the uplink on multi-WAN routers
+ **/
+#[derive(Default, Clone)]
+pub struct Binding {
+    /**
+     * Bind to the device with `SO_BINDTODEVICE`, which needs `CAP_NET_RAW` on Linux before 5.7
+     **/
+    pub interface: Option<String>,
+    pub local_address: Option<IpAddr>
+}
+
+impl Binding {
+    /**
+     * Connect over `family` only, binding to `0.0.0.0` or `::` makes the resolver skip addresses of the other family.
+     * Nothing is built when the local address belongs to the other family.
+     **/
+    pub(super) fn apply(&self, builder: ClientBuilder, family: Family) -> Option<ClientBuilder> {
+        let local_address = match self.local_address {
+            Some(local_address) if !family.matches(&local_address) => return None,
+            Some(local_address) => local_address,
+            None => family.unspecified()
+        };
+        let builder = builder.local_address(local_address);
+        Some(match &self.interface {
+            Some(interface) => bind_interface(builder, interface),
+            None => builder
+        })
+    }
+}
+
+#[cfg(any(
+    target_os = "android",
+    target_os = "fuchsia",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "solaris",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos"
+))]
+fn bind_interface(builder: ClientBuilder, interface: &str) -> ClientBuilder {
+    builder.interface(interface)
+}
+
+#[cfg(not(any(
+    target_os = "android",
+    target_os = "fuchsia",
+    target_os = "illumos",
+    target_os = "ios",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "solaris",
+    target_os = "tvos",
+    target_os = "visionos",
+    target_os = "watchos"
+)))]
+fn bind_interface(builder: ClientBuilder, interface: &str) -> ClientBuilder {
+    use log::warn;
+    warn!(target: "rest_api", "Binding to interface {interface} is not supported on this platform, ignored");
+    builder
+}
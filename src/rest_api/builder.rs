@@ -0,0 +1,60 @@
+use std::time::Duration;
+use reqwest::ClientBuilder;
+
+/**
+ * Why a client could not be built
+ **/
+#[derive(Debug)]
+pub enum Error {
+    /**
+     * The token cannot be sent in an `Authorization` header, e.g. it holds a line break
+     **/
+    InvalidToken,
+    /**
+     * The proxy url is not understood by reqwest
+     **/
+    InvalidProxy,
+    /**
+     * A client was given along with a binding, which only applies to the clients built by the builder
+     **/
+    ClientBinding,
+    /**
+     * Reqwest refused to build the client, e.g. the tls backend failed to initialize
+     **/
+    Client
+}
+
+/**
+ * Limit of a whole request unless set
+ **/
+pub const DEFAULT_TOTAL_TIMEOUT: Duration = Duration::from_secs(300);
+/**
+ * Limit of establishing a connection unless set
+ **/
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(120);
+/**
+ * Limit of waiting for each read of a response unless set
+ **/
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(30);
+
+/**
+ * Timeouts of every request, defaults follow [Hypertext Transfer Protocol (HTTP) Timeouts](https://www.ietf.org/archive/id/draft-thomson-hybi-http-timeout-00.html#rfc.section.1.1)
+ **/
+#[derive(Clone, Copy, Debug)]
+pub(super) struct Timeouts {
+    pub total: Duration,
+    pub connect: Duration,
+    pub read: Duration
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self { total: DEFAULT_TOTAL_TIMEOUT, connect: DEFAULT_CONNECT_TIMEOUT, read: DEFAULT_READ_TIMEOUT }
+    }
+}
+
+impl Timeouts {
+    pub(super) fn apply(self, builder: ClientBuilder) -> ClientBuilder {
+        builder.timeout(self.total).connect_timeout(self.connect).read_timeout(self.read)
+    }
+}
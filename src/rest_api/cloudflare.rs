@@ -1,38 +1,32 @@
 pub mod error;
 pub mod record;
+mod builder;
 mod verify_user_token;
 
+use reqwest::{Method, RequestBuilder};
+
 use super::CloudflareApi;
 use error::Error;
 
+pub use builder::Builder;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 impl CloudflareApi {
-    // noinspection SpellCheckingInspection
-    pub fn new(token: &String, zone: &String, total_timeout: u64, connect_timeout: u64, read_timeout: u64) -> Self {
-        use reqwest::header::HeaderValue;
-        let Ok(authorization_value) = HeaderValue::from_str(&format!("Bearer {}", &token)) else {
-            panic!("Error occurred when building authorization header value");
-        };
+    pub const DEFAULT_BASE_URL: &str = "https://api.cloudflare.com/client/v4";
 
-        use reqwest::header::HeaderMap;
-        let mut headers = HeaderMap::new();
-        use reqwest::header::AUTHORIZATION;
-        headers.insert(AUTHORIZATION, authorization_value);
+    /**
+     * Client of `zone`, authenticated by the api token `token`
+     **/
+    pub fn builder(token: &str, zone: &str) -> Builder {
+        Builder::new(token, zone)
+    }
 
-        use reqwest::Client;
-        use std::time::Duration;
-        let client = Client::builder()
-            .timeout(Duration::from_secs(total_timeout))
-            .connect_timeout(Duration::from_secs(connect_timeout))
-            .read_timeout(Duration::from_secs(read_timeout))
-            .default_headers(headers)
-            .build()
-            .unwrap_or_else(|err|
-                if err.is_body() { panic!("Error occurred when building reqwest client") }
-                else { panic!("Unknown error occurred when building reqwest client") }
-            );
-        let zone = zone.clone();
-        Self { client, zone }
+    /**
+     * Every endpoint is requested with the token, also through a client handed to [Builder::client]
+     **/
+    fn request(&self, method: Method, url: String) -> RequestBuilder {
+        use reqwest::header::AUTHORIZATION;
+        self.client.request(method, url).header(AUTHORIZATION, self.authorization.clone())
     }
-}
\ No newline at end of file
+}
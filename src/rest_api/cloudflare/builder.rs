@@ -0,0 +1,94 @@
+use std::time::Duration;
+use reqwest::Client;
+
+use super::super::{builder::{Error, Timeouts}, CloudflareApi, Proxy};
+
+/**
+ * Options of a [CloudflareApi], started by [CloudflareApi::builder]
+ **/
+pub struct Builder {
+    token: String,
+    zone: String,
+    base_url: String,
+    timeouts: Timeouts,
+    proxy: Proxy,
+    client: Option<Client>
+}
+
+impl Builder {
+    pub(super) fn new(token: &str, zone: &str) -> Self {
+        Self {
+            token: token.to_owned(),
+            zone: zone.to_owned(),
+            base_url: CloudflareApi::DEFAULT_BASE_URL.to_owned(),
+            timeouts: Timeouts::default(),
+            proxy: Proxy::Environment,
+            client: None
+        }
+    }
+
+    /**
+     * The api root every endpoint is appended to, [CloudflareApi::DEFAULT_BASE_URL] unless set,
+     * e.g. a local stub or a corporate api gateway
+     **/
+    pub fn base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_owned();
+        self
+    }
+
+    /**
+     * Limit of a whole request, [300 seconds](crate::rest_api::builder::DEFAULT_TOTAL_TIMEOUT) unless set
+     **/
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.total = timeout;
+        self
+    }
+
+    /**
+     * Limit of establishing a connection, [120 seconds](crate::rest_api::builder::DEFAULT_CONNECT_TIMEOUT) unless set
+     **/
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.connect = timeout;
+        self
+    }
+
+    /**
+     * Limit of waiting for each read of a response, [30 seconds](crate::rest_api::builder::DEFAULT_READ_TIMEOUT) unless set
+     **/
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.read = timeout;
+        self
+    }
+
+    /**
+     * [Proxy::Environment] unless set
+     **/
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /**
+     * Send the requests through `client`, its own timeouts and proxy apply instead of the ones set here
+     **/
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub fn build(self) -> Result<CloudflareApi, Error> {
+        use reqwest::header::HeaderValue;
+        let Ok(mut authorization) = HeaderValue::from_str(&format!("Bearer {}", self.token)) else {
+            return Err(Error::InvalidToken);
+        };
+        authorization.set_sensitive(true);
+
+        let client = match self.client {
+            Some(client) => client,
+            None => self.proxy.apply(self.timeouts.apply(Client::builder()))?
+                .build()
+                .map_err(|_| Error::Client)?
+        };
+        Ok(CloudflareApi { client, authorization, base_url: self.base_url, zone: self.zone })
+    }
+}
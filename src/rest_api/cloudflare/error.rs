@@ -1,5 +1,11 @@
+/**
+ * Why a request to the Cloudflare api failed
+ **/
 #[derive(Debug)]
 pub enum Error {
+    /**
+     * The api rejected the request as malformed, a bug rather than a configuration problem
+     **/
     Internal,
     Network,
     Unauthorized,
@@ -1,4 +1,6 @@
+use std::collections::BTreeMap;
 use serde::Deserialize;
+use serde_json::Value;
 
 mod list;
 mod create;
@@ -7,16 +9,99 @@ mod handle_network_error;
 mod record_type;
 mod update;
 
+pub use create::NewRecord;
 pub use record_type::RecordType;
 
-#[derive(Deserialize)]
+/**
+ * A dns record as the api returns it, see <https://developers.cloudflare.com/api/resources/dns/subresources/records/models/record_response/>
+ * Fields the api adds later are kept in `unknown` instead of failing to deserialize.
+ **/
+#[derive(Deserialize, Clone, Debug)]
 pub struct Record {
     pub id: String,
+    #[serde(default)]
+    pub zone_id: Option<String>,
+    #[serde(default)]
+    pub zone_name: Option<String>,
     #[serde(rename = "name")]
     pub domain_name: String,
     #[serde(rename = "content")]
     pub value: String,
     #[serde(rename = "type")]
     pub record_type: RecordType,
-    pub proxied: bool
+    /**
+     * Whether the record may be proxied at all, assumed when absent
+     **/
+    #[serde(default = "default::proxiable")]
+    pub proxiable: bool,
+    #[serde(default)]
+    pub proxied: bool,
+    /**
+     * 1 stands for automatic
+     **/
+    #[serde(rename = "ttl", default = "default::time_to_live")]
+    pub time_to_live: u32,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub created_on: Option<String>,
+    #[serde(default)]
+    pub modified_on: Option<String>,
+    #[serde(default)]
+    pub settings: Settings,
+    #[serde(default)]
+    pub meta: BTreeMap<String, Value>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>
+}
+
+#[derive(Deserialize, Clone, Default, Debug)]
+pub struct Settings {
+    #[serde(default)]
+    pub ipv4_only: Option<bool>,
+    #[serde(default)]
+    pub ipv6_only: Option<bool>,
+    #[serde(default)]
+    pub flatten_cname: Option<bool>,
+    #[serde(flatten)]
+    pub unknown: BTreeMap<String, Value>
+}
+
+mod default {
+
+    pub(super) fn proxiable() -> bool { true }
+
+    pub(super) fn time_to_live() -> u32 { 1 }
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Record, RecordType};
+
+    #[test]
+    fn test_deserialize() {
+        let record = serde_json::from_str::<Record>(r#"{
+            "id": "023e105f4ecef8ad9ca31a8372d0c353",
+            "name": "example.com",
+            "type": "TXT",
+            "content": "hello",
+            "proxiable": false,
+            "proxied": false,
+            "ttl": 3600,
+            "settings": { "flatten_cname": false, "future_setting": 1 },
+            "meta": {},
+            "comment": null,
+            "tags": [],
+            "priority": 10
+        }"#).unwrap();
+        assert_eq!(record.record_type, RecordType::TXT);
+        assert!(!record.proxiable);
+        assert_eq!(record.time_to_live, 3600);
+        assert_eq!(record.settings.flatten_cname, Some(false));
+        assert_eq!(record.settings.unknown["future_setting"], 1);
+        assert_eq!(record.unknown["priority"], 10);
+    }
 }
@@ -6,18 +6,28 @@ use super::{
     RecordType
 };
 
-#[derive(Serialize)]
-struct RequestBody {
+/**
+ * A record to create, see <https://developers.cloudflare.com/api/resources/dns/subresources/records/methods/create/>
+ **/
+#[derive(Serialize, Clone, Debug)]
+pub struct NewRecord<'a> {
     #[serde(rename = "name")]
-    domain_name: String,
+    pub domain_name: &'a str,
     #[serde(rename = "content")]
-    value: String,
+    pub value: &'a str,
     #[serde(rename = "type")]
-    record_type: RecordType,
+    pub record_type: RecordType,
+    /**
+     * 1 means automatic, which proxied records always get
+     **/
     #[serde(rename = "ttl")]
-    time_to_live: u16,
+    pub time_to_live: u32,
     #[serde(rename = "proxied")]
-    is_proxied: bool
+    pub is_proxied: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<&'a str>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    pub tags: &'a [String]
 }
 
 #[derive(Deserialize)]
@@ -27,13 +37,15 @@ struct ResponseBody {
 }
 
 impl CloudflareApi {
-    pub async fn create_record(
-        &self, domain_name: &String, value: &String, record_type: &RecordType, time_to_live: u16, is_proxied: bool
-    ) -> Result<Record> {
+    /**
+     * The record as created by the api, `comment` and `tags` are left empty when not given
+     **/
+    pub async fn create_record(&self, record: &NewRecord<'_>) -> Result<Record> {
         use super::handle_network_error::HandleReqwestError;
         
-        self.client.post(self.create_record_url())
-            .json(&RequestBody::new(domain_name, value, &record_type, time_to_live, is_proxied))
+        use reqwest::Method;
+        self.request(Method::POST, self.create_record_url())
+            .json(record)
             .send().await
             .handle_reqwest_error()?
             .json::<ResponseBody>().await
@@ -53,24 +65,12 @@ impl CloudflareApi {
     }
 }
 
-impl RequestBody {
-    fn new(domain_name: &String, value: &String, record_type: &RecordType, time_to_live: u16, is_proxied: bool) -> Self {
-        Self {
-            domain_name: domain_name.to_owned(),
-            value: value.to_owned(),
-            record_type: *record_type,
-            time_to_live,
-            is_proxied
-        }
-    }
-}
-
 trait CreateRecordUrl {
     fn create_record_url(&self) -> String;
 }
 
 impl CreateRecordUrl for CloudflareApi {
     fn create_record_url(&self) -> String {
-        format!("https://api.cloudflare.com/client/v4/zones/{}/dns_records", self.zone)
+        format!("{}/zones/{}/dns_records", self.base_url, self.zone)
     }
 }
\ No newline at end of file
@@ -13,10 +13,14 @@ struct ResponseBody {
 }
 
 impl CloudflareApi {
-    pub async fn delete_record(&self, record: &String) -> Result<()> {
+    /**
+     * `record` is the id of the record
+     **/
+    pub async fn delete_record(&self, record: &str) -> Result<()> {
         use super::handle_network_error::HandleReqwestError;
         
-        self.client.delete(self.delete_record_url(record))
+        use reqwest::Method;
+        self.request(Method::DELETE, self.delete_record_url(record))
             .send().await
             .handle_reqwest_error()?
             .json::<ResponseBody>().await
@@ -37,11 +41,11 @@ impl CloudflareApi {
 }
 
 trait DeleteRecordUrl {
-    fn delete_record_url(&self, record: &String) -> String;
+    fn delete_record_url(&self, record: &str) -> String;
 }
 
 impl DeleteRecordUrl for CloudflareApi {
-    fn delete_record_url(&self, record: &String) -> String {
-        format!("https://api.cloudflare.com/client/v4/zones/{}/dns_records/{}", self.zone, record)
+    fn delete_record_url(&self, record: &str) -> String {
+        format!("{}/zones/{}/dns_records/{}", self.base_url, self.zone, record)
     }
 }
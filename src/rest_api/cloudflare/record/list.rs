@@ -9,14 +9,45 @@ use super::{
 struct ResponseBody {
     #[serde(rename = "result")]
     pub records: Vec<Record>,
-    pub success: bool
+    pub success: bool,
+    #[serde(default)]
+    pub result_info: Option<ResultInfo>
 }
 
+#[derive(Deserialize)]
+struct ResultInfo {
+    pub total_pages: u32
+}
+
+/**
+ * Records asked for per page, the default of the api
+ **/
+const PER_PAGE: u32 = 100;
+
 impl CloudflareApi {
+    /**
+     * The records of the zone, following every page the api reports
+     **/
     pub async fn list_record(&self) -> Result<Vec<Record>> {
+        let mut records = Vec::new();
+        let mut page = 1;
+        loop {
+            let response_body = self.list_record_page(page).await?;
+            records.extend(response_body.records);
+            let total_pages = response_body.result_info.map_or(1, |result_info| result_info.total_pages);
+            if page >= total_pages {
+                return Ok(records);
+            }
+            page += 1;
+        }
+    }
+
+    async fn list_record_page(&self, page: u32) -> Result<ResponseBody> {
         use super::handle_network_error::HandleReqwestError;
 
-        self.client.get(self.list_record_url())
+        use reqwest::Method;
+        self.request(Method::GET, self.list_record_url())
+            .query(&[("page", page), ("per_page", PER_PAGE)])
             .send().await
             .handle_reqwest_error()?
             .json::<ResponseBody>().await
@@ -26,7 +57,7 @@ impl CloudflareApi {
             })
             .and_then(|response_body|
                 if response_body.success {
-                    Ok(response_body.records)
+                    Ok(response_body)
                 }
                 else {
                     use super::super::error::Error;
@@ -42,6 +73,27 @@ trait ListRecordsURL {
 
 impl ListRecordsURL for CloudflareApi {
     fn list_record_url(&self) -> String {
-        format!("https://api.cloudflare.com/client/v4/zones/{}/dns_records", &self.zone)
+        format!("{}/zones/{}/dns_records", self.base_url, self.zone)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod test {
+    use super::{CloudflareApi, ListRecordsURL};
+
+    #[test]
+    fn test_list_record_url() {
+        let cloudflare_api = CloudflareApi::builder("token", "zone").base_url("http://127.0.0.1:8080/client/v4/").build().unwrap();
+        assert_eq!(cloudflare_api.list_record_url(), "http://127.0.0.1:8080/client/v4/zones/zone/dns_records");
+    }
+
+    #[test]
+    fn test_build() {
+        use reqwest::Client;
+        use crate::rest_api::{builder::Error, Proxy};
+        assert!(CloudflareApi::builder("token", "zone").client(Client::new()).build().is_ok());
+        assert!(matches!(CloudflareApi::builder("to\nken", "zone").build(), Err(Error::InvalidToken)));
+        let proxy = Proxy::Url(String::from("http://[::1"));
+        assert!(matches!(CloudflareApi::builder("token", "zone").proxy(proxy).build(), Err(Error::InvalidProxy)));
+    }
+}
@@ -1,54 +1,87 @@
+use std::fmt::{Display, Formatter};
 use serde::{Deserialize, Serialize};
 
-#[derive(Serialize, Deserialize, Copy, Clone)]
-pub enum RecordType { A, AAAA, NS }
-
-impl PartialEq for RecordType {
-    fn eq(&self, other: &Self) -> bool {
-        matches!(
-            (self, other),
-            (RecordType::A, RecordType::A) | (RecordType::AAAA, RecordType::AAAA) | (RecordType::NS, RecordType::NS)
-        )
-    }
+/**
+ * Types a zone may hold, see <https://developers.cloudflare.com/api/resources/dns/subresources/records/methods/list/>
+ * Types added to the api later are read as [RecordType::Other] rather than failing the whole listing.
+ **/
+#[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum RecordType {
+    A,
+    AAAA,
+    CAA,
+    CERT,
+    CNAME,
+    DNSKEY,
+    DS,
+    HTTPS,
+    LOC,
+    MX,
+    NAPTR,
+    NS,
+    OPENPGPKEY,
+    PTR,
+    SMIMEA,
+    SRV,
+    SSHFP,
+    SVCB,
+    TLSA,
+    TXT,
+    URI,
+    #[serde(other)]
+    Other
 }
 
-use std::ops::Deref;
-
-impl Deref for RecordType {
-
-    type Target = Self;
-
-    fn deref(&self) -> &Self::Target {
-        // Just return self, as all RecordType are singleton
-        &self
+impl RecordType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordType::A => "A",
+            RecordType::AAAA => "AAAA",
+            RecordType::CAA => "CAA",
+            RecordType::CERT => "CERT",
+            RecordType::CNAME => "CNAME",
+            RecordType::DNSKEY => "DNSKEY",
+            RecordType::DS => "DS",
+            RecordType::HTTPS => "HTTPS",
+            RecordType::LOC => "LOC",
+            RecordType::MX => "MX",
+            RecordType::NAPTR => "NAPTR",
+            RecordType::NS => "NS",
+            RecordType::OPENPGPKEY => "OPENPGPKEY",
+            RecordType::PTR => "PTR",
+            RecordType::SMIMEA => "SMIMEA",
+            RecordType::SRV => "SRV",
+            RecordType::SSHFP => "SSHFP",
+            RecordType::SVCB => "SVCB",
+            RecordType::TLSA => "TLSA",
+            RecordType::TXT => "TXT",
+            RecordType::URI => "URI",
+            RecordType::Other => "OTHER"
+        }
     }
-
 }
 
-const RECORD_TYPE_A: &str = "A";
-const RECORD_TYPE_AAAA: &str = "A";
-const RECORD_TYPE_NS: &str = "NS";
-
-use std::fmt::{Display, Formatter};
-
 impl Display for RecordType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let str = match self {
-            RecordType::A => { RECORD_TYPE_A }
-            RecordType::AAAA => { RECORD_TYPE_AAAA }
-            RecordType::NS => { RECORD_TYPE_NS }
-        };
-        f.write_str(str)
+        f.write_str(self.as_str())
     }
 }
 
 #[cfg(test)]
 mod test {
+    use super::RecordType;
+
     #[test]
     fn test_record_type_display() {
-        use super::RecordType;
-        assert_eq!(format!("{}", RecordType::A), super::RECORD_TYPE_A);
-        assert_eq!(format!("{}", RecordType::AAAA), super::RECORD_TYPE_AAAA);
-        assert_eq!(format!("{}", RecordType::NS), super::RECORD_TYPE_NS);
+        assert_eq!(format!("{}", RecordType::A), "A");
+        assert_eq!(format!("{}", RecordType::AAAA), "AAAA");
+        assert_eq!(format!("{}", RecordType::NS), "NS");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_record_type_unknown() {
+        assert_eq!(serde_json::from_str::<RecordType>(r#""AAAA""#).unwrap(), RecordType::AAAA);
+        assert_eq!(serde_json::from_str::<RecordType>(r#""NEWTYPE""#).unwrap(), RecordType::Other);
+    }
+}
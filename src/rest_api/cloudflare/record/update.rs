@@ -15,6 +15,17 @@ enum RequestBody {
     Proxied {
         #[serde(rename = "proxied")]
         is_proxied: bool
+    },
+    Comment {
+        comment: String
+    },
+    Fields {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        comment: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        tags: Option<Vec<String>>,
+        #[serde(rename = "ttl", skip_serializing_if = "Option::is_none")]
+        time_to_live: Option<u32>
     }
 }
 
@@ -26,10 +37,11 @@ struct ResponseBody {
 
 impl CloudflareApi {
 
-    async fn update_record(&self, record: &String, request_body: RequestBody) -> Result<Record> {
+    async fn update_record(&self, record: &str, request_body: RequestBody) -> Result<Record> {
         use super::handle_network_error::HandleReqwestError;
         
-        self.client.patch(self.update_record_url(record))
+        use reqwest::Method;
+        self.request(Method::PATCH, self.update_record_url(record))
             .json(&request_body)
             .send().await
             .handle_reqwest_error()?
@@ -49,35 +61,59 @@ impl CloudflareApi {
             )
     }
 
-    pub async fn update_record_value(&self, record: &String, value: &String) -> Result<Record> {
+    /**
+     * Point the record `record`, an id, at another address
+     **/
+    pub async fn update_record_value(&self, record: &str, value: &str) -> Result<Record> {
         self.update_record(record, RequestBody::from_value(value)).await
     }
 
-    pub async fn update_record_proxied(&self, record: &String, is_proxied: bool) -> Result<Record> {
+    pub async fn update_record_proxied(&self, record: &str, is_proxied: bool) -> Result<Record> {
         self.update_record(record, RequestBody::from_is_proxied(is_proxied)).await
     }
 
+    pub async fn update_record_comment(&self, record: &str, comment: &str) -> Result<Record> {
+        self.update_record(record, RequestBody::from_comment(comment)).await
+    }
+
+    /**
+     * Replace whichever of comment, tags and ttl is given
+     **/
+    pub async fn update_record_fields(
+        &self, record: &str, comment: Option<&str>, tags: Option<&[String]>, time_to_live: Option<u32>
+    ) -> Result<Record> {
+        self.update_record(record, RequestBody::from_fields(comment, tags, time_to_live)).await
+    }
+
 }
 
 impl RequestBody {
 
-    pub(super) fn from_value(value: &String) -> Self {
-        Self::Value { value: value.clone() }
+    pub(super) fn from_value(value: &str) -> Self {
+        Self::Value { value: value.to_owned() }
     }
 
     pub(super) fn from_is_proxied(is_proxied: bool) -> Self {
         Self::Proxied { is_proxied }
     }
 
+    pub(super) fn from_comment(comment: &str) -> Self {
+        Self::Comment { comment: comment.to_owned() }
+    }
+
+    pub(super) fn from_fields(comment: Option<&str>, tags: Option<&[String]>, time_to_live: Option<u32>) -> Self {
+        Self::Fields { comment: comment.map(str::to_owned), tags: tags.map(<[String]>::to_vec), time_to_live }
+    }
+
 }
 
 trait UpdateRecordUrl {
-    fn update_record_url(&self, record: &String) -> String;
+    fn update_record_url(&self, record: &str) -> String;
 }
 
 impl UpdateRecordUrl for CloudflareApi {
-    fn update_record_url(&self, record: &String) -> String {
-        format!("https://api.cloudflare.com/client/v4/zones/{}/dns_records/{}", self.zone, record)
+    fn update_record_url(&self, record: &str) -> String {
+        format!("{}/zones/{}/dns_records/{}", self.base_url, self.zone, record)
     }
 }
 
@@ -89,13 +125,21 @@ mod test {
     #[test]
     pub fn serialize() {
         assert_eq!(
-            serde_json::to_string(&RequestBody::from_value(&"192.168.1.110".to_string())).unwrap(),
+            serde_json::to_string(&RequestBody::from_value("192.168.1.110")).unwrap(),
             r#"{"content":"192.168.1.110"}"#
         );
         assert_eq!(
             serde_json::to_string(&RequestBody::from_is_proxied(true)).unwrap(),
             r#"{"proxied":true}"#
         );
+        assert_eq!(
+            serde_json::to_string(&RequestBody::from_comment("nas")).unwrap(),
+            r#"{"comment":"nas"}"#
+        );
+        assert_eq!(
+            serde_json::to_string(&RequestBody::from_fields(None, Some(&[String::from("ddns")]), Some(300))).unwrap(),
+            r#"{"tags":["ddns"],"ttl":300}"#
+        );
     }
 
 }
\ No newline at end of file
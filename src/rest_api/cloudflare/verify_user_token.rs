@@ -2,16 +2,18 @@ use serde::Deserialize;
 
 use super::{CloudflareApi, Result};
 
-const URL: &str = "https://api.cloudflare.com/client/v4/user/tokens/verify";
-
 #[derive(Deserialize)]
 struct ResponseBody {
     pub success: bool
 }
 
 impl CloudflareApi {
+    /**
+     * Fails with [super::error::Error::Unauthorized] unless the token is active
+     **/
     pub async fn verify_user_token(&self) -> Result<()> {
-        self.client.get(URL)
+        use reqwest::Method;
+        self.request(Method::GET, self.verify_user_token_url())
             .send().await
             .map_err(|error| {
                 use reqwest::StatusCode;
@@ -37,4 +39,14 @@ impl CloudflareApi {
                 }
             )
     }
+}
+
+trait VerifyUserTokenUrl {
+    fn verify_user_token_url(&self) -> String;
+}
+
+impl VerifyUserTokenUrl for CloudflareApi {
+    fn verify_user_token_url(&self) -> String {
+        format!("{}/user/tokens/verify", self.base_url)
+    }
 }
\ No newline at end of file
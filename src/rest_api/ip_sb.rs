@@ -1,25 +1,22 @@
 pub mod error;
 pub mod ip;
+mod builder;
 
-use super::IpSBApi;
+use super::{Family, IpSBApi};
 use error::Error;
 
+pub use builder::Builder;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 impl IpSBApi {
-    pub fn new(total_timeout: u64, connect_timeout: u64, read_timeout: u64) -> Self {
-        use reqwest::Client;
-        use std::time::Duration;
-        let client = Client::builder()
-            .timeout(Duration::from_secs(total_timeout))
-            .connect_timeout(Duration::from_secs(connect_timeout))
-            .read_timeout(Duration::from_secs(read_timeout))
-            .build()
-            .unwrap_or_else(|error| 
-                if error.is_body() { panic!("Error occurred when building reqwest client") }
-                else { panic!("Unknown error occurred when building reqwest client") }
-            );
-        
-        Self { client }
+    pub const DEFAULT_V4_URL: &str = "https://api-ipv4.ip.sb/jsonip";
+    pub const DEFAULT_V6_URL: &str = "https://api-ipv6.ip.sb/jsonip";
+
+    /**
+     * Detector of both families through [Self::DEFAULT_V4_URL] and [Self::DEFAULT_V6_URL] unless told otherwise
+     **/
+    pub fn builder() -> Builder {
+        Builder::new()
     }
-}
\ No newline at end of file
+}
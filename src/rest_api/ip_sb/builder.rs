@@ -0,0 +1,116 @@
+use std::time::Duration;
+use reqwest::Client;
+
+use super::super::{builder::{Error, Timeouts}, Binding, Family, IpSBApi, Proxy};
+
+/**
+ * Options of an [IpSBApi], started by [IpSBApi::builder]
+ **/
+pub struct Builder {
+    v4_url: String,
+    v6_url: String,
+    timeouts: Timeouts,
+    proxy: Proxy,
+    binding: Binding,
+    client: Option<Client>
+}
+
+impl Builder {
+    pub(super) fn new() -> Self {
+        Self {
+            v4_url: IpSBApi::DEFAULT_V4_URL.to_owned(),
+            v6_url: IpSBApi::DEFAULT_V6_URL.to_owned(),
+            timeouts: Timeouts::default(),
+            proxy: Proxy::Direct,
+            binding: Binding::default(),
+            client: None
+        }
+    }
+
+    /**
+     * Echo service of the IPv4 address, [IpSBApi::DEFAULT_V4_URL] unless set
+     **/
+    pub fn v4_url(mut self, url: &str) -> Self {
+        self.v4_url = url.to_owned();
+        self
+    }
+
+    /**
+     * Echo service of the IPv6 address, [IpSBApi::DEFAULT_V6_URL] unless set
+     **/
+    pub fn v6_url(mut self, url: &str) -> Self {
+        self.v6_url = url.to_owned();
+        self
+    }
+
+    /**
+     * Limit of a whole request, [300 seconds](crate::rest_api::builder::DEFAULT_TOTAL_TIMEOUT) unless set
+     **/
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.total = timeout;
+        self
+    }
+
+    /**
+     * Limit of establishing a connection, [120 seconds](crate::rest_api::builder::DEFAULT_CONNECT_TIMEOUT) unless set
+     **/
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.connect = timeout;
+        self
+    }
+
+    /**
+     * Limit of waiting for each read of a response, [30 seconds](crate::rest_api::builder::DEFAULT_READ_TIMEOUT) unless set
+     **/
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.timeouts.read = timeout;
+        self
+    }
+
+    /**
+     * [Proxy::Direct] unless set, through a proxy the address of the proxy is detected
+     **/
+    pub fn proxy(mut self, proxy: Proxy) -> Self {
+        self.proxy = proxy;
+        self
+    }
+
+    /**
+     * Picks the uplink whose address is detected, the default route unless set
+     **/
+    pub fn binding(mut self, binding: Binding) -> Self {
+        self.binding = binding;
+        self
+    }
+
+    /**
+     * Send the requests of both families through `client`, its own timeouts and proxy apply instead of the ones set here.
+     * It connects over either family, so a single dual-stack url may report the same family twice,
+     * and building fails with [Error::ClientBinding] when a binding is set as well.
+     **/
+    pub fn client(mut self, client: Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /**
+     * Each family gets its own client connecting over that family only,
+     * so any echo service, including a single dual-stack url for both, reports the right address.
+     **/
+    pub fn build(self) -> Result<IpSBApi, Error> {
+        let (v4_client, v6_client) = match self.client {
+            Some(_) if self.binding.interface.is_some() || self.binding.local_address.is_some() => return Err(Error::ClientBinding),
+            Some(client) => (Some(client.clone()), Some(client)),
+            None => {
+                let build = |family| -> Result<Option<Client>, Error> {
+                    let builder = self.proxy.apply(self.timeouts.apply(Client::builder()))?;
+                    self.binding.apply(builder, family)
+                        .map(|builder| builder.build().map_err(|_| Error::Client))
+                        .transpose()
+                };
+                (build(Family::V4)?, build(Family::V6)?)
+            }
+        };
+        Ok(IpSBApi { v4_client, v6_client, v4_url: self.v4_url, v6_url: self.v6_url })
+    }
+}
@@ -1,3 +1,6 @@
+/**
+ * Why no address was detected, the most telling error of both families
+ **/
 #[derive(Debug)]
 pub enum Error {
     Network,
@@ -1,8 +1,11 @@
 use reqwest::Client;
 use serde::Deserialize;
 
-use super::{IpSBApi, Result};
+use super::{Family, IpSBApi, Result};
 
+/**
+ * The detected addresses, a family the host has no route for is missing
+ **/
 pub enum IP {
     V4(String),
     V6(String),
@@ -27,15 +30,14 @@ impl IP {
 
 }
 
-mod url { 
-    pub(super) const V4: &str = "https://api-ipv4.ip.sb/jsonip";
-    pub(super) const V6: &str = "https://api-ipv6.ip.sb/jsonip";
-}
-
 impl IpSBApi {
     pub async fn get_ip(&self) -> Result<IP> {
         use tokio::join;
-        match join!(self.client.send_request_to(url::V4), self.client.send_request_to(url::V6)) {
+        let (v4, v6) = (
+            request(self.v4_client.as_ref(), &self.v4_url, Family::V4),
+            request(self.v6_client.as_ref(), &self.v6_url, Family::V6)
+        );
+        match join!(v4, v6) {
             (Ok(v4), Ok(v6)) => Ok(IP::Both { v4, v6 }),
             (Ok(v4), Err(_)) => Ok(IP::V4(v4)),
             (Err(_), Ok(v6)) => Ok(IP::V6(v6)),
@@ -45,14 +47,26 @@ impl IpSBApi {
                 if matches!(v4_err, Error::Network) || matches!(v6_err, Error::Network) { Err(Error::Network) }
                 // Any single server error will cause to server error returned
                 else if matches!(v4_err, Error::Server) || matches!(v6_err, Error::Server) { Err(Error::Server) }
+                else if matches!(v4_err, Error::DecodeResponse) || matches!(v6_err, Error::DecodeResponse) { Err(Error::DecodeResponse) }
                 else { Err(Error::Unknown) }
             }
         }
     }
 }
 
+/**
+ * No client means the family cannot be reached through the configured binding
+ **/
+async fn request(client: Option<&Client>, api_url: &str, family: Family) -> Result<String> {
+    let Some(client) = client else {
+        use super::error::Error;
+        return Err(Error::Unknown);
+    };
+    client.send_request_to(api_url, family).await
+}
+
 trait SendRequest {
-    async fn send_request_to(&self, api_url: &str) -> Result<String>;
+    async fn send_request_to(&self, api_url: &str, family: Family) -> Result<String>;
 }
 
 #[derive(Deserialize)]
@@ -61,8 +75,9 @@ struct ResponseBody {
 }
 
 impl SendRequest for Client {
-    async fn send_request_to(&self, api_url: &str) -> Result<String> {
+    async fn send_request_to(&self, api_url: &str, family: Family) -> Result<String> {
         self.get(api_url).send().await
+            .and_then(|response| response.error_for_status())
             .map_err(|error| {
                 use reqwest::StatusCode;
                 use super::error::Error;
@@ -70,44 +85,79 @@ impl SendRequest for Client {
                     // When incorrect user input is entered,
                     // the server returns an HTTP 400 Error (Bad Request),
                     // along with a JSON-encoded error message.
-                    Some(status_code) if status_code == StatusCode::BAD_REQUEST => Error::Server,
+                    Some(status_code) if status_code == StatusCode::BAD_REQUEST || status_code.is_server_error() => Error::Server,
                     _ if error.is_connect() || error.is_request() || error.is_timeout() => Error::Network,
                     _ => Error::Unknown
                 }
             })?
-            .json::<ResponseBody>().await
-            .map(|response_body| response_body.ip)
-            .map_err(|error| {
+            .text().await
+            .map_err(|_| {
                 use super::error::Error;
-                if error.is_decode() { Error::DecodeResponse } else { Error::Network }
+                Error::Network
             })
+            .and_then(|body| parse_ip(&body, family))
+    }
+}
+
+/**
+ * Accept `{"ip": "..."}` as ip.sb and ipify respond, or the bare address as most echo services do,
+ * an address of the other family is rejected
+ **/
+fn parse_ip(body: &str, family: Family) -> Result<String> {
+    let ip = match serde_json::from_str::<ResponseBody>(body) {
+        Ok(response_body) => response_body.ip,
+        Err(_) => body.trim().to_string()
+    };
+    use std::net::IpAddr;
+    match ip.parse::<IpAddr>() {
+        Ok(address) if family.matches(&address) => Ok(address.to_string()),
+        _ => {
+            use super::error::Error;
+            Err(Error::DecodeResponse)
+        }
     }
 }
 
 #[cfg(test)]
 mod test {
-    use log::info;
-    
-    use super::{IP, IpSBApi};
+    use crate::rest_api::mock::ip::MockIp;
+
+    use super::{parse_ip, Family, IP, IpSBApi};
+
+    #[test]
+    fn test_parse_ip() {
+        assert_eq!(parse_ip(r#"{"ip":"203.0.113.1"}"#, Family::V4).unwrap(), "203.0.113.1");
+        assert_eq!(parse_ip("2001:DB8::1\n", Family::V6).unwrap(), "2001:db8::1");
+        assert!(parse_ip("203.0.113.1", Family::V6).is_err());
+        assert!(parse_ip("<html></html>", Family::V4).is_err());
+    }
 
     #[tokio::test]
     async fn test_ip() {
-        match IpSBApi::new(300, 120, 30).get_ip().await {
-            Ok(ip) => match ip {
-                IP::V4(v4) => {
-                    info!("IPv4={v4}");
-                    println!("IPv4={v4}");
-                }
-                IP::V6(v6) => {
-                    info!("IPv6={v6}");
-                    println!("IPv6={v6}");
-                }
-                IP::Both { v4, v6 } => {
-                    info!("IPv4={v4} IPv6={v6}");
-                    println!("IPv4={v4} IPv6={v6}");
-                }
-            },
-            Err(err) => panic!("{:?}", err)
-        }
+        let mock = MockIp::start(Some("203.0.113.1"), Some("2001:db8::1")).await;
+        let ip = IpSBApi::builder().v4_url(&mock.v4_url).v6_url(&mock.v6_url).build().unwrap().get_ip().await.unwrap();
+        assert!(matches!(ip, IP::Both { ref v4, ref v6 } if v4 == "203.0.113.1" && v6 == "2001:db8::1"));
+
+        mock.set(None, Some("2001:db8::1"));
+        let ip = IpSBApi::builder().v4_url(&mock.v4_url).v6_url(&mock.v6_url).build().unwrap().get_ip().await.unwrap();
+        assert!(matches!(ip, IP::V6(ref v6) if v6 == "2001:db8::1"));
+
+        // A family is never detected over the other one, even when both point to the same url
+        let ip = IpSBApi::builder().v4_url(&mock.v6_url).v6_url(&mock.v6_url).build().unwrap().get_ip().await.unwrap();
+        assert!(matches!(ip, IP::V6(_)));
+
+        mock.set(None, None);
+        assert!(IpSBApi::builder().v4_url(&mock.v4_url).v6_url(&mock.v6_url).build().unwrap().get_ip().await.is_err());
+
+        // An injected client is used for both families as it is
+        use reqwest::Client;
+        mock.set(Some("203.0.113.1"), Some("2001:db8::1"));
+        let ip_sb_api = IpSBApi::builder().v4_url(&mock.v4_url).v6_url(&mock.v6_url).client(Client::new()).build().unwrap();
+        assert!(matches!(ip_sb_api.get_ip().await.unwrap(), IP::Both { .. }));
+
+        // A binding cannot be applied to it
+        use crate::rest_api::{builder::Error, Binding};
+        let binding = Binding { interface: None, local_address: Some("127.0.0.1".parse().unwrap()) };
+        assert!(matches!(IpSBApi::builder().binding(binding).client(Client::new()).build(), Err(Error::ClientBinding)));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,15 @@
+pub mod ip;
+
+use std::net::SocketAddr;
+use axum::Router;
+
+/**
+ * Serve `router` on an ephemeral port of `host` until the test runtime shuts down
+ **/
+pub(crate) async fn serve(host: &str, router: Router) -> SocketAddr {
+    use tokio::net::TcpListener;
+    let listener = TcpListener::bind((host, 0)).await.unwrap();
+    let address = listener.local_addr().unwrap();
+    tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+    address
+}
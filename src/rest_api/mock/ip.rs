@@ -0,0 +1,52 @@
+use std::sync::{Arc, Mutex};
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+    Json,
+    Router
+};
+use serde_json::json;
+
+type Addresses = Arc<Mutex<(Option<String>, Option<String>)>>;
+
+/**
+ * Fake ip echo service answering like `https://api-ipv4.ip.sb/jsonip`,
+ * `/ipv4` is served on `127.0.0.1` and `/ipv6` on `::1` as clients connect over the requested family only.
+ * A family set to `None` answers 503 as if unreachable.
+ **/
+pub struct MockIp {
+    pub v4_url: String,
+    pub v6_url: String,
+    state: Addresses
+}
+
+impl MockIp {
+
+    pub async fn start(v4: Option<&str>, v6: Option<&str>) -> Self {
+        let state: Addresses = Arc::new(Mutex::new((v4.map(str::to_string), v6.map(str::to_string))));
+        let router = Router::new()
+            .route("/ipv4", get(|State(state): State<Addresses>| async move {
+                respond(state.lock().unwrap().0.clone())
+            }))
+            .route("/ipv6", get(|State(state): State<Addresses>| async move {
+                respond(state.lock().unwrap().1.clone())
+            }))
+            .with_state(state.clone());
+        let (v4_address, v6_address) = (super::serve("127.0.0.1", router.clone()).await, super::serve("::1", router).await);
+        Self { v4_url: format!("http://{v4_address}/ipv4"), v6_url: format!("http://{v6_address}/ipv6"), state }
+    }
+
+    pub fn set(&self, v4: Option<&str>, v6: Option<&str>) {
+        *self.state.lock().unwrap() = (v4.map(str::to_string), v6.map(str::to_string));
+    }
+
+}
+
+fn respond(ip: Option<String>) -> Response {
+    match ip {
+        Some(ip) => Json(json!({ "ip": ip })).into_response(),
+        None => (StatusCode::SERVICE_UNAVAILABLE, "Service Unavailable").into_response()
+    }
+}
@@ -0,0 +1,138 @@
+use std::{
+    collections::BTreeMap,
+    path::Path
+};
+use serde::{Deserialize, Serialize};
+
+use super::{notification::Event, status::Status};
+
+/**
+ * What has to outlive a single check, persisted to `state-file` when configured and kept in memory otherwise
+ **/
+#[derive(Deserialize, Serialize, Default)]
+pub struct State {
+    /**
+     * Records whose address family went missing, keyed by `name/type`
+     **/
+    #[serde(default)]
+    missing: BTreeMap<String, Missing>,
+    /**
+     * Consecutive checks with at least one failure
+     **/
+    #[serde(default)]
+    pub failed_checks: u32,
+    /**
+     * Whether the ongoing failure was notified already, so a persistent outage notifies once
+     **/
+    #[serde(default)]
+    pub failure_notified: bool,
+    /**
+     * Changes to notify, kept until the end of the check
+     **/
+    #[serde(skip)]
+    pub events: Vec<Event>,
+    #[serde(skip)]
+    pub failures: Vec<String>,
+    /**
+     * Outcome of the ongoing check, replaced by every check
+     **/
+    #[serde(skip)]
+    pub status: Status
+}
+
+#[derive(Deserialize, Serialize, Clone, Copy)]
+struct Missing {
+    /**
+     * Unix time of the first check the family was missing
+     **/
+    since: u64,
+    checks: u32
+}
+
+impl State {
+
+    /**
+     * An unreadable state starts over rather than stopping the checks
+     **/
+    pub fn load(path: Option<&Path>) -> Self {
+        let Some(path) = path else { return Self::default(); };
+        use std::fs::read_to_string;
+        let Ok(content) = read_to_string(path) else { return Self::default(); };
+        serde_json::from_str(&content).unwrap_or_else(|error| {
+            use log::warn;
+            warn!(target: "state", "Discard unreadable state file {}: {error}", path.display());
+            Self::default()
+        })
+    }
+
+    /**
+     * A crash never leaves a truncated state behind
+     **/
+    pub fn save(&self, path: &Path) {
+        let Ok(content) = serde_json::to_string_pretty(self) else { return; };
+        if let Err(error) = write_atomically(path, &content) {
+            use log::error;
+            error!(target: "state", "Failed to write state file {}: {error}", path.display());
+        }
+    }
+
+    /**
+     * Count one more check the address of `key` is missing, returning the consecutive checks and seconds since first missed
+     **/
+    pub fn missing(&mut self, key: &str, now: u64) -> (u32, u64) {
+        let missing = self.missing.entry(key.to_string())
+            .and_modify(|missing| missing.checks += 1)
+            .or_insert(Missing { since: now, checks: 1 });
+        (missing.checks, now.saturating_sub(missing.since))
+    }
+
+    pub fn present(&mut self, key: &str) {
+        self.missing.remove(key);
+    }
+
+    pub fn notify(&mut self, event: Event) {
+        self.events.push(event);
+    }
+
+    pub fn fail(&mut self, message: String) {
+        if !self.failures.contains(&message) {
+            self.failures.push(message);
+        }
+    }
+
+}
+
+/**
+ * Written to a sibling file then renamed over
+ **/
+pub fn write_atomically(path: &Path, content: &str) -> std::io::Result<()> {
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(".tmp");
+
+    use std::fs::{rename, write};
+    write(&temporary, content).and_then(|_| rename(&temporary, path))
+}
+
+pub fn now() -> u64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|duration| duration.as_secs()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod test {
+    use super::State;
+
+    #[test]
+    fn test_missing_round_trip() {
+        let path = std::env::temp_dir().join(format!("cfddns-state-{}.json", std::process::id()));
+        let mut state = State::default();
+        assert_eq!(state.missing("home.example.com/AAAA", 100), (1, 0));
+        state.save(&path);
+
+        let mut state = State::load(Some(&path));
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(state.missing("home.example.com/AAAA", 400), (2, 300));
+        state.present("home.example.com/AAAA");
+        assert_eq!(state.missing("home.example.com/AAAA", 500), (1, 0));
+    }
+}
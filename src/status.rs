@@ -0,0 +1,132 @@
+use std::path::Path;
+use serde::Serialize;
+
+use super::configuration::config::status::StatusFormat;
+
+/**
+ * What the last check did, written to `status-file` for cron users to inspect or alert on
+ **/
+#[derive(Serialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub struct Status {
+    /**
+     * Unix time the check started
+     **/
+    pub timestamp: u64,
+    pub succeeded: bool,
+    pub duration_seconds: f64,
+    pub sources: Vec<SourceStatus>,
+    pub records: Vec<RecordStatus>
+}
+
+/**
+ * Addresses detected by a source, `source` naming the provider used
+ **/
+#[derive(Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SourceStatus {
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v4: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v6: Option<String>,
+    pub duration_seconds: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>
+}
+
+#[derive(Serialize)]
+pub struct RecordStatus {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub record_type: String,
+    pub outcome: Outcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>
+}
+
+#[derive(Serialize, Clone, Copy, PartialEq, Debug)]
+#[serde(rename_all = "kebab-case")]
+pub enum Outcome {
+    Unchanged,
+    Updated,
+    Created,
+    Deleted,
+    /**
+     * Left alone as unowned, vetoed by `pre-change` or kept while its address family is missing
+     **/
+    Skipped,
+    /**
+     * Not brought in line, including when the records could not be listed or no address of its family was detected
+     **/
+    Failed
+}
+
+impl Status {
+
+    pub fn record(&mut self, name: &str, record_type: &str, outcome: Outcome, error: Option<String>) {
+        self.records.push(RecordStatus { name: name.to_string(), record_type: record_type.to_string(), outcome, error });
+    }
+
+    /**
+     * Written like the state file, a reader never sees a half written status
+     **/
+    pub fn write(&self, path: &Path, format: StatusFormat) {
+        let content = match format {
+            StatusFormat::Json => match serde_json::to_string_pretty(self) {
+                Ok(content) => content,
+                Err(_) => return
+            },
+            StatusFormat::Textfile => self.textfile()
+        };
+
+        use super::state::write_atomically;
+        if let Err(error) = write_atomically(path, &content) {
+            use log::error;
+            error!(target: "status", "Failed to write status file {}: {error}", path.display());
+        }
+    }
+
+    /**
+     * node_exporter textfile collector format, `status-file` has to end in `.prom` to be collected
+     **/
+    fn textfile(&self) -> String {
+        use super::metrics::escape;
+        let mut lines = vec![
+            String::from("# HELP cfddns_status_timestamp_seconds Unix time the last check started"),
+            String::from("# TYPE cfddns_status_timestamp_seconds gauge"),
+            format!("cfddns_status_timestamp_seconds {}", self.timestamp),
+            String::from("# HELP cfddns_status_succeeded Whether the last check went without failure"),
+            String::from("# TYPE cfddns_status_succeeded gauge"),
+            format!("cfddns_status_succeeded {}", u8::from(self.succeeded)),
+            String::from("# HELP cfddns_status_duration_seconds Duration of the last check"),
+            String::from("# TYPE cfddns_status_duration_seconds gauge"),
+            format!("cfddns_status_duration_seconds {}", self.duration_seconds),
+            String::from("# HELP cfddns_status_record_outcome Outcome of each record in the last check"),
+            String::from("# TYPE cfddns_status_record_outcome gauge")
+        ];
+        for record in &self.records {
+            let outcome = serde_json::to_value(record.outcome).ok().and_then(|outcome| outcome.as_str().map(str::to_string)).unwrap_or_default();
+            lines.push(format!(
+                "cfddns_status_record_outcome{{name=\"{}\",type=\"{}\",outcome=\"{outcome}\"}} 1", escape(&record.name), record.record_type
+            ));
+        }
+        lines.join("\n") + "\n"
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Outcome, Status};
+
+    #[test]
+    fn test_textfile() {
+        let mut status = Status { timestamp: 100, succeeded: true, duration_seconds: 0.5, ..Status::default() };
+        status.record("home.example.com", "A", Outcome::Updated, None);
+        let textfile = status.textfile();
+        assert!(textfile.contains("cfddns_status_timestamp_seconds 100\n"));
+        assert!(textfile.contains("cfddns_status_succeeded 1\n"));
+        assert!(textfile.contains(r#"cfddns_status_record_outcome{name="home.example.com",type="A",outcome="updated"} 1"#));
+    }
+}
@@ -0,0 +1,681 @@
+/**
+ * End-to-end checks of `run` against in-process fakes, no network required
+ **/
+pub(crate) mod mock;
+
+use super::{
+    configuration::{
+        cloudflare::{domain_name::{source::Source, DomainName, DomainType}, pattern::Pattern, Cloudflare},
+        config::{
+            notification::{EventKind, Notification, Preset},
+            provider::Provider,
+            smtp::{Security, Smtp},
+            unavailable::UnavailableAction,
+            Config
+        },
+        Configuration
+    },
+    rest_api::Proxy,
+    ownership::MARKER,
+    cli::run,
+    daemon::accept_reload,
+    state::State
+};
+use mock::{cloudflare::{MockCloudflare, MockRecord}, ip::MockIp, smtp::MockSmtp, webhook::MockWebhook};
+
+const HOME: &str = "home.example.com";
+
+/**
+ * A proxied domain name fed by the default source, tests spell out only what they change
+ **/
+fn domain_name(name: &str, domain_type: DomainType) -> DomainName {
+    DomainName {
+        name: name.to_string(),
+        domain_type,
+        proxied: true,
+        time_to_live: 1,
+        source: None,
+        provider: None,
+        ipv6_suffix: None,
+        adopt: false,
+        comment: None,
+        tags: None,
+        pattern: false
+    }
+}
+
+fn configuration(cloudflare: &MockCloudflare, ip: &MockIp, token: &str, proxied: Option<bool>) -> Configuration {
+    Configuration {
+        cloudflare: Cloudflare {
+            token: token.to_string(),
+            zone: MockCloudflare::ZONE.to_string(),
+            zone_name: Some(MockCloudflare::ZONE_NAME.to_string()),
+            domain_names: vec![domain_name(HOME, DomainType::A), domain_name(HOME, DomainType::AAAA)],
+            ..Cloudflare::default()
+        },
+        config: Config {
+            cloudflare_api_url: Some(cloudflare.base_url.clone()),
+            ip_sb_url_v4: Some(ip.v4_url.clone()),
+            ip_sb_url_v6: Some(ip.v6_url.clone()),
+            // Keep a proxy of the environment away from the local fakes
+            cloudflare_proxy: Proxy::Direct,
+            ..Config::default()
+        },
+        proxied,
+        print_config: None,
+        daemon: false,
+        paths: Vec::new()
+    }
+}
+
+#[tokio::test]
+async fn test_first_run_creates_records() {
+    let cloudflare = MockCloudflare::start(Vec::new()).await;
+    let ip = MockIp::start(Some("203.0.113.1"), Some("2001:db8::1")).await;
+
+    run(&configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None), &mut State::default()).await.unwrap();
+
+    let a = cloudflare.record(HOME, "A").expect("A record created");
+    assert_eq!(a.content, "203.0.113.1");
+    assert!(a.proxied);
+    assert_eq!(a.comment.as_deref(), Some(MARKER));
+    let aaaa = cloudflare.record(HOME, "AAAA").expect("AAAA record created");
+    assert_eq!(aaaa.content, "2001:db8::1");
+}
+
+#[tokio::test]
+async fn test_ip_change_updates_records() {
+    let mut state = State::default();
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", true).with_comment(MARKER)
+    ]).await;
+    let ip = MockIp::start(Some("203.0.113.1"), Some("2001:db8::1")).await;
+    let configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    run(&configuration, &mut state).await.unwrap();
+
+    ip.set(Some("198.51.100.7"), Some("2001:db8::7"));
+    run(&configuration, &mut state).await.unwrap();
+
+    assert_eq!(cloudflare.records().len(), 2);
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().content, "198.51.100.7");
+    assert_eq!(cloudflare.record(HOME, "AAAA").unwrap().content, "2001:db8::7");
+}
+
+#[tokio::test]
+async fn test_ipv6_unavailable_hides_record() {
+    let mut state = State::default();
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", true).with_comment(MARKER)
+    ]).await;
+    let ip = MockIp::start(Some("203.0.113.1"), None).await;
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.config.unavailable_hide = false;
+    run(&configuration, &mut state).await.unwrap();
+    assert!(cloudflare.record(HOME, "AAAA").is_some());
+
+    configuration.config.unavailable_hide = true;
+    run(&configuration, &mut state).await.unwrap();
+    assert!(cloudflare.record(HOME, "AAAA").is_none());
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().content, "203.0.113.1");
+}
+
+#[tokio::test]
+async fn test_proxied_toggle() {
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", false).with_comment(MARKER)
+    ]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), Some("2001:db8::7")).await;
+
+    run(&configuration(&cloudflare, &ip, MockCloudflare::TOKEN, Some(false)), &mut State::default()).await.unwrap();
+
+    let a = cloudflare.record(HOME, "A").unwrap();
+    assert!(!a.proxied);
+    // Toggling proxied leaves the address alone
+    assert_eq!(a.content, "203.0.113.1");
+    // Already unproxied, nothing to send
+    use axum::http::Method;
+    assert_eq!(cloudflare.mutations(), vec![(Method::PATCH, format!("/zones/{}/dns_records/a", MockCloudflare::ZONE))]);
+}
+
+#[tokio::test]
+async fn test_proxied_toggle_without_record() {
+    let cloudflare = MockCloudflare::start(vec![MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER)]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), None).await;
+    let path = std::env::temp_dir().join(format!("cfddns-status-proxied-{}.json", std::process::id()));
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, Some(false));
+    configuration.config.status_file = Some(path.clone());
+    run(&configuration, &mut State::default()).await.unwrap();
+    let status: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(status["records"], serde_json::json!([
+        { "name": HOME, "type": "A", "outcome": "updated" },
+        { "name": HOME, "type": "AAAA", "outcome": "failed", "error": "No record to toggle proxied on" }
+    ]));
+}
+
+#[tokio::test]
+async fn test_records_span_pages() {
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("other", "other.example.com", "A", "203.0.113.1", false),
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", true).with_comment(MARKER)
+    ]).await;
+    cloudflare.set_max_per_page(1);
+    let ip = MockIp::start(Some("198.51.100.7"), Some("2001:db8::7")).await;
+
+    run(&configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None), &mut State::default()).await.unwrap();
+    // Records past the first page are updated in place rather than created again
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().content, "198.51.100.7");
+    assert_eq!(cloudflare.record(HOME, "AAAA").unwrap().content, "2001:db8::7");
+    assert_eq!(cloudflare.records().len(), 3);
+}
+
+#[tokio::test]
+async fn test_auth_failure() {
+    let cloudflare = MockCloudflare::start(vec![MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER)]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), Some("2001:db8::7")).await;
+
+    assert!(run(&configuration(&cloudflare, &ip, "wrong-token", None), &mut State::default()).await.is_err());
+    assert!(cloudflare.mutations().is_empty());
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().content, "203.0.113.1");
+}
+
+#[tokio::test]
+async fn test_sources_feed_their_own_domain_names() {
+    let mut state = State::default();
+    let cloudflare = MockCloudflare::start(Vec::new()).await;
+    let (wan1, wan2) = (
+        MockIp::start(Some("203.0.113.1"), None).await,
+        MockIp::start(Some("198.51.100.7"), None).await
+    );
+    let mut configuration = configuration(&cloudflare, &wan1, MockCloudflare::TOKEN, None);
+    configuration.config.providers = vec![Provider {
+        name: String::from("wan2"),
+        url_v4: Some(wan2.v4_url.clone()),
+        url_v6: Some(wan2.v6_url.clone()),
+        bind_interface: None,
+        local_address: Some("127.0.0.1".parse().unwrap())
+    }];
+    configuration.cloudflare.domain_names.push(DomainName {
+        proxied: false,
+        provider: Some(String::from("wan2")),
+        ..domain_name("backup.example.com", DomainType::A)
+    });
+    configuration.cloudflare.domain_names.push(DomainName {
+        proxied: false,
+        source: Some(Source::Static("2001:db8::9".parse().unwrap())),
+        ..domain_name("printer.example.com", DomainType::AAAA)
+    });
+
+    run(&configuration, &mut state).await.unwrap();
+
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().content, "203.0.113.1");
+    assert_eq!(cloudflare.record("backup.example.com", "A").unwrap().content, "198.51.100.7");
+    assert_eq!(cloudflare.record("printer.example.com", "AAAA").unwrap().content, "2001:db8::9");
+}
+
+#[tokio::test]
+async fn test_ipv6_suffix_follows_prefix() {
+    let mut state = State::default();
+    let cloudflare = MockCloudflare::start(Vec::new()).await;
+    let ip = MockIp::start(None, Some("2001:db8:1:2::1")).await;
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.cloudflare.domain_names.push(DomainName {
+        proxied: false,
+        ipv6_suffix: Some("::1234:5678:9abc:def0".parse().unwrap()),
+        ..domain_name("nas.example.com", DomainType::AAAA)
+    });
+    run(&configuration, &mut state).await.unwrap();
+    assert_eq!(cloudflare.record("nas.example.com", "AAAA").unwrap().content, "2001:db8:1:2:1234:5678:9abc:def0");
+
+    ip.set(None, Some("2001:db8:9:8::1"));
+    run(&configuration, &mut state).await.unwrap();
+    assert_eq!(cloudflare.record("nas.example.com", "AAAA").unwrap().content, "2001:db8:9:8:1234:5678:9abc:def0");
+    assert_eq!(cloudflare.record(HOME, "AAAA").unwrap().content, "2001:db8:9:8::1");
+}
+
+#[tokio::test]
+async fn test_delegated_prefix_ignores_address_rotation() {
+    let mut state = State::default();
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("nas", "nas.example.com", "AAAA", "2001:db8:1:0:1234:5678:9abc:def0", false).with_comment(MARKER)
+    ]).await;
+    let ip = MockIp::start(None, None).await;
+    let lease = std::env::temp_dir().join(format!("cfddns-test-{}.env", std::process::id()));
+    std::fs::write(&lease, "PREFIXES='2001:db8:1::/56,3600,7200'\n").unwrap();
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.cloudflare.domain_names = vec![DomainName {
+        proxied: false,
+        source: Some(Source::DelegatedPrefix(lease.clone())),
+        ipv6_suffix: Some("::1234:5678:9abc:def0".parse().unwrap()),
+        ..domain_name("nas.example.com", DomainType::AAAA)
+    }];
+    run(&configuration, &mut state).await.unwrap();
+    assert!(cloudflare.mutations().is_empty());
+
+    std::fs::write(&lease, "PREFIXES='2001:db8:7::/56,3600,7200'\n").unwrap();
+    run(&configuration, &mut state).await.unwrap();
+    std::fs::remove_file(&lease).unwrap();
+    assert_eq!(cloudflare.record("nas.example.com", "AAAA").unwrap().content, "2001:db8:7:0:1234:5678:9abc:def0");
+    assert_eq!(cloudflare.mutations().len(), 1);
+}
+
+#[tokio::test]
+async fn test_unowned_records_are_left_alone_unless_adopted() {
+    let mut state = State::default();
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", true).with_comment("hand made")
+    ]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), None).await;
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    run(&configuration, &mut state).await.unwrap();
+    assert!(cloudflare.mutations().is_empty());
+
+    configuration.cloudflare.domain_names.iter_mut().for_each(|domain_name| domain_name.adopt = true);
+    run(&configuration, &mut state).await.unwrap();
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().content, "198.51.100.7");
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().comment.as_deref(), Some(MARKER));
+    assert!(cloudflare.record(HOME, "AAAA").is_none());
+}
+
+#[tokio::test]
+async fn test_unavailable_grace_and_park() {
+    let mut state = State::default();
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", true).with_comment(MARKER)
+    ]).await;
+    let ip = MockIp::start(Some("203.0.113.1"), None).await;
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.config.unavailable_checks = 2;
+    configuration.config.unavailable_action = UnavailableAction::Park;
+    configuration.config.park.v6 = Some("100::".parse().unwrap());
+    run(&configuration, &mut state).await.unwrap();
+    assert_eq!(cloudflare.record(HOME, "AAAA").unwrap().content, "2001:db8::1");
+
+    // Flapping back resets the count
+    ip.set(Some("203.0.113.1"), Some("2001:db8::1"));
+    run(&configuration, &mut state).await.unwrap();
+    ip.set(Some("203.0.113.1"), None);
+    run(&configuration, &mut state).await.unwrap();
+    assert_eq!(cloudflare.record(HOME, "AAAA").unwrap().content, "2001:db8::1");
+
+    run(&configuration, &mut state).await.unwrap();
+    assert_eq!(cloudflare.record(HOME, "AAAA").unwrap().content, "100::");
+
+    // Parked already, and without a placeholder to park with
+    let path = std::env::temp_dir().join(format!("cfddns-status-park-{}.json", std::process::id()));
+    configuration.config.status_file = Some(path.clone());
+    let status = |path: &std::path::Path| -> serde_json::Value {
+        serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+    };
+    run(&configuration, &mut state).await.unwrap();
+    assert_eq!(status(&path)["records"][1], serde_json::json!({ "name": HOME, "type": "AAAA", "outcome": "unchanged" }));
+    configuration.config.park.v6 = None;
+    run(&configuration, &mut state).await.unwrap();
+    assert_eq!(
+        status(&path)["records"][1],
+        serde_json::json!({ "name": HOME, "type": "AAAA", "outcome": "failed", "error": "No park address configured for AAAA" })
+    );
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(cloudflare.record(HOME, "AAAA").unwrap().content, "100::");
+}
+
+#[tokio::test]
+async fn test_notifications() {
+    let cloudflare = MockCloudflare::start(Vec::new()).await;
+    let ip = MockIp::start(Some("203.0.113.1"), None).await;
+    let webhook = MockWebhook::start().await;
+    let notification = |preset, path: &str, events| Notification {
+        preset,
+        url: format!("{}{path}", webhook.base_url),
+        chat_id: None,
+        events,
+        template: None,
+        headers: Default::default()
+    };
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.config.notifications = vec![
+        notification(Preset::Generic, "/generic", Vec::new()),
+        notification(Preset::Slack, "/slack", vec![EventKind::Failed, EventKind::Recovered])
+    ];
+    configuration.config.notify_failures_after = 2;
+    let mut state = State::default();
+    run(&configuration, &mut state).await.unwrap();
+
+    let requests = webhook.requests();
+    assert_eq!(requests.len(), 1);
+    let (path, content_type, body) = &requests[0];
+    assert_eq!((path.as_str(), content_type.as_str()), ("/generic", "application/json"));
+    let body: serde_json::Value = serde_json::from_str(body).unwrap();
+    assert_eq!(body["event"], "created");
+    assert_eq!(body["name"], HOME);
+    assert_eq!(body["new"], "203.0.113.1");
+
+    // A persistent failure notifies once after the threshold, then its recovery
+    configuration.cloudflare.token = String::from("wrong-token");
+    for _ in 0..3 {
+        assert!(run(&configuration, &mut state).await.is_err());
+    }
+    configuration.cloudflare.token = MockCloudflare::TOKEN.to_string();
+    run(&configuration, &mut state).await.unwrap();
+
+    let slack = webhook.requests().into_iter()
+        .filter(|(path, _, _)| path == "/slack")
+        .map(|(_, _, body)| serde_json::from_str::<serde_json::Value>(&body).unwrap()["text"].as_str().unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(slack.len(), 2);
+    assert!(slack[0].starts_with("Check failed: "));
+    assert_eq!(slack[1], "Check recovered");
+    assert_eq!(webhook.requests().len(), 5);
+}
+
+#[tokio::test]
+async fn test_email_notifications() {
+    let cloudflare = MockCloudflare::start(vec![MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER)]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), None).await;
+    let smtp = MockSmtp::start().await;
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.config.smtp = Some(Smtp {
+        host: String::from("127.0.0.1"),
+        port: Some(smtp.port),
+        security: Security::None,
+        username: None,
+        password: None,
+        from: String::from("ddns@example.com"),
+        to: vec![String::from("ops@example.com")],
+        events: Vec::new(),
+        timeout: 5
+    });
+    let mut state = State::default();
+    run(&configuration, &mut state).await.unwrap();
+
+    configuration.cloudflare.token = String::from("wrong-token");
+    assert!(run(&configuration, &mut state).await.is_err());
+
+    let mails = smtp.mails();
+    assert_eq!(mails.len(), 2);
+    assert_eq!(mails[0].from, "ddns@example.com");
+    assert_eq!(mails[0].to, vec![String::from("ops@example.com")]);
+    assert!(mails[0].data.contains(&format!("Update {HOME} (A) 203.0.113.1 -> 198.51.100.7")));
+    assert!(mails[1].data.contains("Check failed: "));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_hooks() {
+    let cloudflare = MockCloudflare::start(vec![MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER)]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), None).await;
+    let output = std::env::temp_dir().join(format!("cfddns-hook-{}", std::process::id()));
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.config.pre_change = Some(String::from("exit 1"));
+    run(&configuration, &mut State::default()).await.unwrap();
+    assert!(cloudflare.mutations().is_empty());
+
+    configuration.config.pre_change = Some(String::from(r#"test "$CFDDNS_NEW_IPV4" = 198.51.100.7"#));
+    configuration.config.on_change = Some(format!(
+        r#"echo "$CFDDNS_ACTION $CFDDNS_RECORD $CFDDNS_OLD_IPV4 $CFDDNS_NEW_IPV4" > {}"#, output.display()
+    ));
+    run(&configuration, &mut State::default()).await.unwrap();
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().content, "198.51.100.7");
+    let changed = std::fs::read_to_string(&output).unwrap();
+    std::fs::remove_file(&output).unwrap();
+    assert_eq!(changed, format!("update {HOME} 203.0.113.1 198.51.100.7\n"));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_hooks_cover_proxied_toggle_and_adoption() {
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", true)
+    ]).await;
+    let ip = MockIp::start(None, None).await;
+    let output = std::env::temp_dir().join(format!("cfddns-modify-hook-{}", std::process::id()));
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, Some(false));
+    configuration.cloudflare.domain_names.iter_mut().for_each(|domain_name| domain_name.adopt = true);
+    configuration.config.pre_change = Some(String::from("exit 1"));
+    run(&configuration, &mut State::default()).await.unwrap();
+    // Neither the proxied toggle nor the adoption comment gets past the veto
+    assert!(cloudflare.mutations().is_empty());
+
+    configuration.cloudflare.domain_names.iter_mut().for_each(|domain_name| domain_name.adopt = false);
+    configuration.config.pre_change = Some(String::from(r#"test "$CFDDNS_ACTION" = modify"#));
+    configuration.config.on_change = Some(format!(
+        r#"echo "$CFDDNS_ACTION $CFDDNS_RECORD $CFDDNS_OLD $CFDDNS_NEW" > {}"#, output.display()
+    ));
+    run(&configuration, &mut State::default()).await.unwrap();
+    assert!(!cloudflare.record(HOME, "A").unwrap().proxied);
+    let changed = std::fs::read_to_string(&output).unwrap();
+    std::fs::remove_file(&output).unwrap();
+    assert_eq!(changed, format!("modify {HOME} proxied=true proxied=false\n"));
+}
+
+#[tokio::test]
+async fn test_status_file() {
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", true).with_comment(MARKER)
+    ]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), Some("2001:db8::1")).await;
+    let path = std::env::temp_dir().join(format!("cfddns-status-{}.json", std::process::id()));
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.config.status_file = Some(path.clone());
+    run(&configuration, &mut State::default()).await.unwrap();
+
+    let status: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(status["succeeded"], true);
+    assert_eq!(status["sources"][0]["source"], "wan");
+    assert_eq!(status["sources"][0]["v4"], "198.51.100.7");
+    assert_eq!(status["records"], serde_json::json!([
+        { "name": HOME, "type": "A", "outcome": "updated" },
+        { "name": HOME, "type": "AAAA", "outcome": "unchanged" }
+    ]));
+}
+
+#[tokio::test]
+async fn test_status_covers_every_domain_name() {
+    let cloudflare = MockCloudflare::start(vec![MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER)]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), None).await;
+    let path = std::env::temp_dir().join(format!("cfddns-status-every-{}.json", std::process::id()));
+
+    // Failing before any record is known
+    let mut configuration = configuration(&cloudflare, &ip, "wrong-token", None);
+    configuration.config.status_file = Some(path.clone());
+    assert!(run(&configuration, &mut State::default()).await.is_err());
+    let records: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    let error = "Unauthorized responded when verifying token availability from Cloudflare api";
+    assert_eq!(records["records"], serde_json::json!([
+        { "name": HOME, "type": "A", "outcome": "failed", "error": error },
+        { "name": HOME, "type": "AAAA", "outcome": "failed", "error": error }
+    ]));
+
+    // Neither a record nor an address to create one with
+    configuration.cloudflare.token = MockCloudflare::TOKEN.to_string();
+    configuration.config.unavailable_hide = false;
+    run(&configuration, &mut State::default()).await.unwrap();
+    let records: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(records["records"], serde_json::json!([
+        { "name": HOME, "type": "A", "outcome": "updated" },
+        { "name": HOME, "type": "AAAA", "outcome": "failed", "error": "No AAAA address detected for the record" }
+    ]));
+}
+
+#[tokio::test]
+async fn test_patterns_follow_existing_records() {
+    let cloudflare = MockCloudflare::start(vec![
+        MockRecord::new("home", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("a", "a.home.example.com", "A", "203.0.113.1", false).with_comment(MARKER),
+        MockRecord::new("b", "b.c.home.example.com", "A", "203.0.113.1", false).with_comment(MARKER),
+        MockRecord::new("tagged", "tagged.example.com", "A", "203.0.113.1", false).with_comment(MARKER).with_tag("ddns:home"),
+        MockRecord::new("other", "other.example.com", "A", "203.0.113.1", false).with_comment(MARKER)
+    ]).await;
+    let ip = MockIp::start(Some("198.51.100.7"), None).await;
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    let pattern = |name: Option<&str>, tag: Option<&str>| Pattern {
+        name: name.map(str::to_string),
+        tag: tag.map(str::to_string),
+        domain_type: DomainType::A,
+        source: None,
+        provider: None,
+        ipv6_suffix: None,
+        adopt: false
+    };
+    configuration.cloudflare.patterns = vec![pattern(Some("*.home.example.com"), None), pattern(None, Some("ddns"))];
+    run(&configuration, &mut State::default()).await.unwrap();
+
+    for name in [HOME, "a.home.example.com", "b.c.home.example.com", "tagged.example.com"] {
+        assert_eq!(cloudflare.record(name, "A").unwrap().content, "198.51.100.7", "{name}");
+    }
+    assert_eq!(cloudflare.record("other.example.com", "A").unwrap().content, "203.0.113.1");
+    // Matched records keep their own settings and are never created for the missing family
+    assert!(!cloudflare.record("a.home.example.com", "A").unwrap().proxied);
+    assert_eq!(cloudflare.records().len(), 5);
+}
+
+#[tokio::test]
+async fn test_comment_and_tags() {
+    let cloudflare = MockCloudflare::start(Vec::new()).await;
+    let ip = MockIp::start(Some("203.0.113.1"), None).await;
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.cloudflare.domain_names.truncate(1);
+    configuration.cloudflare.domain_names[0].comment = Some(String::from("{name} from {provider}"));
+    configuration.cloudflare.domain_names[0].tags = Some(vec![String::from("ddns")]);
+    run(&configuration, &mut State::default()).await.unwrap();
+
+    let record = cloudflare.record(HOME, "A").unwrap();
+    assert_eq!(record.comment, Some(format!("{HOME} from wan {MARKER}")));
+    assert_eq!(record.tags, vec![String::from("ddns")]);
+
+    // Reconciled without an address change, a comment rendering the time would wait for one
+    configuration.cloudflare.domain_names[0].comment = Some(String::from("nas"));
+    configuration.cloudflare.domain_names[0].tags = Some(vec![String::from("ddns"), String::from("site:home")]);
+    run(&configuration, &mut State::default()).await.unwrap();
+    let record = cloudflare.record(HOME, "A").unwrap();
+    assert_eq!(record.comment, Some(format!("nas {MARKER}")));
+    assert_eq!(record.tags, vec![String::from("ddns"), String::from("site:home")]);
+
+    configuration.cloudflare.domain_names[0].comment = Some(String::from("updated at {time}"));
+    run(&configuration, &mut State::default()).await.unwrap();
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().comment, Some(format!("nas {MARKER}")));
+    ip.set(Some("198.51.100.7"), None);
+    run(&configuration, &mut State::default()).await.unwrap();
+    assert!(cloudflare.record(HOME, "A").unwrap().comment.unwrap().starts_with("updated at 20"));
+}
+
+#[tokio::test]
+async fn test_record_model_drives_reconciliation() {
+    let mut record = MockRecord::new("a", HOME, "A", "203.0.113.1", false).with_comment(MARKER).not_proxiable();
+    record.ttl = 3600;
+    let cloudflare = MockCloudflare::start(vec![record]).await;
+    let ip = MockIp::start(Some("203.0.113.1"), None).await;
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, Some(true));
+    configuration.cloudflare.domain_names.truncate(1);
+    run(&configuration, &mut State::default()).await.unwrap();
+    assert!(cloudflare.mutations().is_empty());
+
+    // A drifted ttl is brought back along with the address check
+    configuration.proxied = None;
+    run(&configuration, &mut State::default()).await.unwrap();
+    assert_eq!(cloudflare.record(HOME, "A").unwrap().ttl, 1);
+}
+
+#[tokio::test]
+async fn test_proxied_records_keep_automatic_ttl() {
+    let cloudflare = MockCloudflare::start(vec![MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER)]).await;
+    let ip = MockIp::start(Some("203.0.113.1"), None).await;
+
+    let mut configuration = configuration(&cloudflare, &ip, MockCloudflare::TOKEN, None);
+    configuration.cloudflare.domain_names.truncate(1);
+    configuration.cloudflare.domain_names[0].time_to_live = 300;
+    // A ttl Cloudflare never takes for a proxied record is not asked for on every check
+    run(&configuration, &mut State::default()).await.unwrap();
+    run(&configuration, &mut State::default()).await.unwrap();
+    assert!(cloudflare.mutations().is_empty());
+
+    ip.set(Some("198.51.100.7"), None);
+    run(&configuration, &mut State::default()).await.unwrap();
+    let record = cloudflare.record(HOME, "A").unwrap();
+    assert_eq!(record.content, "198.51.100.7");
+    assert_eq!(record.ttl, 1);
+}
+
+/**
+ * The configuration before a reload, also publishing `old.example.com` and cleaning up removed domain names
+ **/
+fn before_reload(cloudflare: &MockCloudflare, ip: &MockIp) -> Configuration {
+    let mut configuration = configuration(cloudflare, ip, MockCloudflare::TOKEN, None);
+    let mut old = configuration.cloudflare.domain_names[0].clone();
+    old.name = String::from("old.example.com");
+    configuration.cloudflare.domain_names.push(old);
+    configuration.config.cleanup_removed = true;
+    configuration
+}
+
+fn reload_records() -> Vec<MockRecord> {
+    vec![
+        MockRecord::new("a", HOME, "A", "203.0.113.1", true).with_comment(MARKER),
+        MockRecord::new("aaaa", HOME, "AAAA", "2001:db8::1", true).with_comment(MARKER),
+        MockRecord::new("old", "old.example.com", "A", "203.0.113.1", true).with_comment(MARKER)
+    ]
+}
+
+#[tokio::test]
+async fn test_reload_deletes_removed_domain_names() {
+    let cloudflare = MockCloudflare::start(reload_records()).await;
+    let ip = MockIp::start(Some("203.0.113.1"), Some("2001:db8::1")).await;
+
+    let mut reloaded = before_reload(&cloudflare, &ip);
+    reloaded.cloudflare.domain_names.pop();
+    let reloaded = accept_reload(&before_reload(&cloudflare, &ip), Ok(reloaded), &mut State::default()).await;
+
+    assert!(reloaded.is_some_and(|reloaded| reloaded.cloudflare.domain_names.len() == 2));
+    assert!(cloudflare.record("old.example.com", "A").is_none());
+    assert_eq!(cloudflare.mutations().len(), 1);
+}
+
+#[tokio::test]
+async fn test_reload_keeps_kept_domain_names() {
+    let cloudflare = MockCloudflare::start(reload_records()).await;
+    let ip = MockIp::start(Some("203.0.113.1"), Some("2001:db8::1")).await;
+
+    // Only the letter case differs, still the same domain name
+    let mut reloaded = before_reload(&cloudflare, &ip);
+    reloaded.cloudflare.domain_names[2].name = String::from("OLD.example.com");
+    assert!(accept_reload(&before_reload(&cloudflare, &ip), Ok(reloaded), &mut State::default()).await.is_some());
+
+    assert_eq!(cloudflare.records().len(), 3);
+    assert!(cloudflare.mutations().is_empty());
+}
+
+#[tokio::test]
+async fn test_rejected_reload_keeps_current_configuration() {
+    let cloudflare = MockCloudflare::start(reload_records()).await;
+    let ip = MockIp::start(Some("203.0.113.1"), Some("2001:db8::1")).await;
+
+    use crate::configuration::error::Error;
+    let reloaded = accept_reload(&before_reload(&cloudflare, &ip), Err(Error::ZoneMissing), &mut State::default()).await;
+
+    assert!(reloaded.is_none());
+    assert_eq!(cloudflare.records().len(), 3);
+    assert!(cloudflare.mutations().is_empty());
+}
@@ -0,0 +1,6 @@
+pub mod cloudflare;
+pub mod smtp;
+pub mod webhook;
+
+pub use crate::rest_api::mock::ip;
+use crate::rest_api::mock::serve;
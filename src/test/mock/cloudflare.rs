@@ -0,0 +1,271 @@
+use std::{collections::HashMap, sync::{Arc, Mutex}};
+use axum::{
+    extract::{Path, Query, State},
+    http::{HeaderMap, Method, StatusCode},
+    response::{IntoResponse, Response},
+    routing::get,
+    Json,
+    Router
+};
+use serde_json::{json, Value};
+
+/**
+ * In-process fake of the Cloudflare dns records api, mirroring the bodies of
+ * https://developers.cloudflare.com/api/resources/dns/subresources/records/
+ **/
+pub struct MockCloudflare {
+    pub base_url: String,
+    state: Arc<Mutex<MockState>>
+}
+
+#[derive(Clone)]
+pub struct MockRecord {
+    pub id: String,
+    pub name: String,
+    pub record_type: String,
+    pub content: String,
+    pub proxiable: bool,
+    pub proxied: bool,
+    pub ttl: u64,
+    pub comment: Option<String>,
+    pub tags: Vec<String>
+}
+
+struct MockState {
+    token: String,
+    zone: String,
+    records: Vec<MockRecord>,
+    requests: Vec<(Method, String)>,
+    next_id: usize,
+    max_per_page: usize
+}
+
+impl MockCloudflare {
+
+    pub const TOKEN: &str = "mock-token";
+    pub const ZONE: &str = "023e105f4ecef8ad9ca31a8372d0c353";
+    pub const ZONE_NAME: &str = "example.com";
+
+    pub async fn start(records: Vec<MockRecord>) -> Self {
+        let state = Arc::new(Mutex::new(MockState {
+            token: Self::TOKEN.to_string(),
+            zone: Self::ZONE.to_string(),
+            records,
+            requests: Vec::new(),
+            next_id: 1,
+            max_per_page: 5_000_000
+        }));
+        let router = Router::new()
+            .route("/client/v4/user/tokens/verify", get(verify))
+            .route("/client/v4/zones/{zone}/dns_records", get(list).post(create))
+            .route("/client/v4/zones/{zone}/dns_records/{id}", axum::routing::patch(update).delete(delete))
+            .with_state(state.clone());
+        let address = super::serve("127.0.0.1", router).await;
+        Self { base_url: format!("http://{address}/client/v4"), state }
+    }
+
+    pub fn records(&self) -> Vec<MockRecord> {
+        self.state.lock().unwrap().records.clone()
+    }
+
+    pub fn record(&self, name: &str, record_type: &str) -> Option<MockRecord> {
+        self.records().into_iter().find(|record| record.name == name && record.record_type == record_type)
+    }
+
+    /**
+     * Cap `per_page` of the record list below what is asked for, so the records span several pages
+     **/
+    pub fn set_max_per_page(&self, max_per_page: usize) {
+        self.state.lock().unwrap().max_per_page = max_per_page;
+    }
+
+    /**
+     * Requests received so far other than token verification, e.g. `(Method::PATCH, "/zones/../dns_records/..")`
+     **/
+    pub fn mutations(&self) -> Vec<(Method, String)> {
+        self.state.lock().unwrap().requests.iter()
+            .filter(|(method, _)| *method != Method::GET)
+            .cloned()
+            .collect()
+    }
+
+}
+
+impl MockRecord {
+
+    pub fn new(id: &str, name: &str, record_type: &str, content: &str, proxied: bool) -> Self {
+        Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            content: content.to_string(),
+            proxiable: true,
+            proxied,
+            ttl: 1,
+            comment: None,
+            tags: Vec::new()
+        }
+    }
+
+    pub fn with_comment(mut self, comment: &str) -> Self {
+        self.comment = Some(comment.to_string());
+        self
+    }
+
+    pub fn not_proxiable(mut self) -> Self {
+        self.proxiable = false;
+        self
+    }
+
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "id": self.id,
+            "zone_id": MockCloudflare::ZONE,
+            "zone_name": MockCloudflare::ZONE_NAME,
+            "name": self.name,
+            "type": self.record_type,
+            "content": self.content,
+            "proxiable": self.proxiable,
+            "proxied": self.proxied,
+            "ttl": self.ttl,
+            "settings": {},
+            "meta": {},
+            "comment": self.comment,
+            "tags": self.tags,
+            "created_on": "2014-01-01T05:20:00.12345Z",
+            "modified_on": "2014-01-01T05:20:00.12345Z"
+        })
+    }
+
+}
+
+fn success(result: Value) -> Response {
+    (StatusCode::OK, Json(json!({ "result": result, "success": true, "errors": [], "messages": [] }))).into_response()
+}
+
+fn failure(status_code: StatusCode, code: u32, message: &str) -> Response {
+    let body = json!({ "result": null, "success": false, "errors": [{ "code": code, "message": message }], "messages": [] });
+    (status_code, Json(body)).into_response()
+}
+
+/**
+ * Record the request and authenticate it, the error response is returned when rejected
+ **/
+fn reject(state: &Mutex<MockState>, headers: &HeaderMap, method: Method, path: String, zone: Option<&str>) -> Option<Response> {
+    let mut state = state.lock().unwrap();
+    state.requests.push((method, path));
+    let authorization = headers.get("authorization").and_then(|value| value.to_str().ok());
+    if authorization != Some(&format!("Bearer {}", state.token)) {
+        return Some(failure(StatusCode::UNAUTHORIZED, 10000, "Authentication error"));
+    }
+    if zone.is_some_and(|zone| zone != state.zone) {
+        return Some(failure(StatusCode::FORBIDDEN, 10000, "Authentication error"));
+    }
+    None
+}
+
+async fn verify(State(state): State<Arc<Mutex<MockState>>>, headers: HeaderMap) -> Response {
+    if let Some(response) = reject(&state, &headers, Method::GET, String::from("/user/tokens/verify"), None) {
+        return response;
+    }
+    success(json!({ "id": "ed17574386854bf78a67040be0a770b0", "status": "active" }))
+}
+
+async fn list(
+    State(state): State<Arc<Mutex<MockState>>>, Path(zone): Path<String>, Query(query): Query<HashMap<String, usize>>, headers: HeaderMap
+) -> Response {
+    let path = format!("/zones/{zone}/dns_records");
+    if let Some(response) = reject(&state, &headers, Method::GET, path, Some(&zone)) {
+        return response;
+    }
+    let state = state.lock().unwrap();
+    let page = query.get("page").copied().unwrap_or(1).max(1);
+    let per_page = query.get("per_page").copied().unwrap_or(100).clamp(1, state.max_per_page);
+    let records = state.records.iter().skip((page - 1) * per_page).take(per_page).map(MockRecord::to_json).collect::<Vec<_>>();
+    let result_info = json!({
+        "page": page,
+        "per_page": per_page,
+        "count": records.len(),
+        "total_count": state.records.len(),
+        "total_pages": state.records.len().div_ceil(per_page)
+    });
+    let body = json!({ "result": records, "result_info": result_info, "success": true, "errors": [], "messages": [] });
+    (StatusCode::OK, Json(body)).into_response()
+}
+
+async fn create(
+    State(state): State<Arc<Mutex<MockState>>>, Path(zone): Path<String>, headers: HeaderMap, Json(body): Json<Value>
+) -> Response {
+    let path = format!("/zones/{zone}/dns_records");
+    if let Some(response) = reject(&state, &headers, Method::POST, path, Some(&zone)) {
+        return response;
+    }
+    let (Some(name), Some(record_type), Some(content)) = (body["name"].as_str(), body["type"].as_str(), body["content"].as_str()) else {
+        return failure(StatusCode::BAD_REQUEST, 9207, "Request body is invalid.");
+    };
+
+    let mut state = state.lock().unwrap();
+    let id = format!("{:032x}", state.next_id);
+    state.next_id += 1;
+    let mut record = MockRecord::new(&id, name, record_type, content, body["proxied"].as_bool().unwrap_or(false));
+    // Like Cloudflare, proxied records keep a ttl of 1 (automatic) whatever is asked for
+    record.ttl = if record.proxied { 1 } else { body["ttl"].as_u64().unwrap_or(1) };
+    record.comment = body["comment"].as_str().map(str::to_string);
+    record.tags = body["tags"].as_array()
+        .map(|tags| tags.iter().filter_map(Value::as_str).map(str::to_string).collect())
+        .unwrap_or_default();
+    state.records.push(record.clone());
+    success(record.to_json())
+}
+
+async fn update(
+    State(state): State<Arc<Mutex<MockState>>>, Path((zone, id)): Path<(String, String)>, headers: HeaderMap, Json(body): Json<Value>
+) -> Response {
+    let path = format!("/zones/{zone}/dns_records/{id}");
+    if let Some(response) = reject(&state, &headers, Method::PATCH, path, Some(&zone)) {
+        return response;
+    }
+    let mut state = state.lock().unwrap();
+    let Some(record) = state.records.iter_mut().find(|record| record.id == id) else {
+        return failure(StatusCode::NOT_FOUND, 81044, "Record does not exist.");
+    };
+    if let Some(content) = body["content"].as_str() {
+        record.content = content.to_string();
+    }
+    if let Some(proxied) = body["proxied"].as_bool() {
+        record.proxied = proxied;
+    }
+    if let Some(ttl) = body["ttl"].as_u64() {
+        record.ttl = ttl;
+    }
+    if record.proxied {
+        record.ttl = 1;
+    }
+    if let Some(comment) = body.get("comment") {
+        record.comment = comment.as_str().map(str::to_string);
+    }
+    if let Some(tags) = body["tags"].as_array() {
+        record.tags = tags.iter().filter_map(Value::as_str).map(str::to_string).collect();
+    }
+    success(record.to_json())
+}
+
+async fn delete(
+    State(state): State<Arc<Mutex<MockState>>>, Path((zone, id)): Path<(String, String)>, headers: HeaderMap
+) -> Response {
+    let path = format!("/zones/{zone}/dns_records/{id}");
+    if let Some(response) = reject(&state, &headers, Method::DELETE, path, Some(&zone)) {
+        return response;
+    }
+    let mut state = state.lock().unwrap();
+    let Some(index) = state.records.iter().position(|record| record.id == id) else {
+        return failure(StatusCode::NOT_FOUND, 81044, "Record does not exist.");
+    };
+    state.records.remove(index);
+    success(json!({ "id": id }))
+}
@@ -0,0 +1,85 @@
+use std::sync::{Arc, Mutex};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream
+};
+
+/**
+ * A delivered mail
+ **/
+#[derive(Clone, Default, Debug)]
+pub struct Mail {
+    pub from: String,
+    pub to: Vec<String>,
+    pub data: String
+}
+
+type Mails = Arc<Mutex<Vec<Mail>>>;
+
+/**
+ * Plain text smtp sink on `127.0.0.1` accepting every mail without authentication
+ **/
+pub struct MockSmtp {
+    pub port: u16,
+    mails: Mails
+}
+
+impl MockSmtp {
+
+    pub async fn start() -> Self {
+        use tokio::net::TcpListener;
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let mails: Mails = Arc::default();
+        let sink = mails.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(session(stream, sink.clone()));
+            }
+        });
+        Self { port, mails }
+    }
+
+    pub fn mails(&self) -> Vec<Mail> {
+        self.mails.lock().unwrap().clone()
+    }
+
+}
+
+async fn session(stream: TcpStream, mails: Mails) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+    let mut mail = Mail::default();
+    writer.write_all(b"220 localhost ESMTP\r\n").await.unwrap();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let command = line.to_ascii_uppercase();
+        let reply: &[u8] = if command.starts_with("EHLO") || command.starts_with("HELO") {
+            b"250 localhost\r\n"
+        }
+        else if let Some(from) = line.strip_prefix("MAIL FROM:") {
+            mail.from = from.trim_matches(['<', '>', ' ']).to_string();
+            b"250 OK\r\n"
+        }
+        else if let Some(to) = line.strip_prefix("RCPT TO:") {
+            mail.to.push(to.trim_matches(['<', '>', ' ']).to_string());
+            b"250 OK\r\n"
+        }
+        else if command == "DATA" {
+            writer.write_all(b"354 End data with <CR><LF>.<CR><LF>\r\n").await.unwrap();
+            while let Ok(Some(line)) = lines.next_line().await && line != "." {
+                mail.data.push_str(&line);
+                mail.data.push('\n');
+            }
+            mails.lock().unwrap().push(std::mem::take(&mut mail));
+            b"250 OK\r\n"
+        }
+        else if command == "QUIT" {
+            writer.write_all(b"221 Bye\r\n").await.unwrap();
+            return;
+        }
+        else {
+            b"250 OK\r\n"
+        };
+        writer.write_all(reply).await.unwrap();
+    }
+}
@@ -0,0 +1,41 @@
+use std::sync::{Arc, Mutex};
+use axum::{
+    body::Bytes,
+    extract::State,
+    http::{header::CONTENT_TYPE, HeaderMap, Uri},
+    routing::post,
+    Router
+};
+
+/**
+ * (path, content type, body)
+ **/
+type Requests = Arc<Mutex<Vec<(String, String, String)>>>;
+
+/**
+ * Fake webhook receiver recording every post on any path
+ **/
+pub struct MockWebhook {
+    pub base_url: String,
+    requests: Requests
+}
+
+impl MockWebhook {
+
+    pub async fn start() -> Self {
+        let requests: Requests = Arc::default();
+        let router = Router::new()
+            .route("/{*path}", post(|State(requests): State<Requests>, uri: Uri, headers: HeaderMap, body: Bytes| async move {
+                let content_type = headers.get(CONTENT_TYPE).and_then(|value| value.to_str().ok()).unwrap_or_default().to_string();
+                requests.lock().unwrap().push((uri.path().to_string(), content_type, String::from_utf8_lossy(&body).to_string()));
+            }))
+            .with_state(requests.clone());
+        let address = super::serve("127.0.0.1", router).await;
+        Self { base_url: format!("http://{address}"), requests }
+    }
+
+    pub fn requests(&self) -> Vec<(String, String, String)> {
+        self.requests.lock().unwrap().clone()
+    }
+
+}